@@ -1,6 +1,7 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
 use ninja_tools::data_structure::bit::BIT;
+use ninja_tools::graph::graph::Graph;
 use ninja_tools::graph::simple_graph::SimpleGraph;
 
 fn bit_bench(c: &mut Criterion) {
@@ -33,5 +34,91 @@ fn min_dists_bench(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, bit_bench, min_dists_bench);
+fn edge_list_graph_min_dists_bench(c: &mut Criterion) {
+    let mut group = c.benchmark_group("graph");
+    group.sample_size(10);
+    group.bench_function("edge list graph min_dist bench", |b| {
+        b.iter(|| {
+            let mut graph = Graph::new(3000, false);
+            for i in 0..3000 {
+                graph.add_edge(black_box(i), i * 2 % 3000, 1usize);
+            }
+            for i in 0..3000 {
+                graph.add_edge(black_box(i), (i + 200) % 3000, 1usize);
+            }
+
+            graph.all_min_dists();
+        })
+    });
+}
+
+fn min_dists_radix_heap_bench(c: &mut Criterion) {
+    let mut group = c.benchmark_group("graph");
+    group.sample_size(10);
+    group.bench_function("graph min_dist radix heap bench", |b| {
+        b.iter(|| {
+            let mut graph = SimpleGraph::new(3000, false);
+            for i in 0..3000 {
+                graph.add_edge(black_box(i), i * 2 % 3000, 1usize);
+            }
+            for i in 0..3000 {
+                graph.add_edge(black_box(i), (i + 200) % 3000, 1usize);
+            }
+
+            for i in 0..3000 {
+                graph.min_dists_radix_heap(i);
+            }
+        })
+    });
+}
+
+fn min_dists_01_bench(c: &mut Criterion) {
+    let mut group = c.benchmark_group("graph");
+    group.sample_size(10);
+    group.bench_function("graph min_dist 01 bench", |b| {
+        b.iter(|| {
+            let mut graph = SimpleGraph::new(3000, false);
+            for i in 0..3000 {
+                graph.add_edge(black_box(i), i * 2 % 3000, i % 2);
+            }
+            for i in 0..3000 {
+                graph.add_edge(black_box(i), (i + 200) % 3000, 1usize);
+            }
+
+            for i in 0..3000 {
+                graph.min_dists_01(i);
+            }
+        })
+    });
+}
+
+fn min_dists_dial_bench(c: &mut Criterion) {
+    let mut group = c.benchmark_group("graph");
+    group.sample_size(10);
+    group.bench_function("graph min_dist dial bench", |b| {
+        b.iter(|| {
+            let mut graph = SimpleGraph::new(3000, false);
+            for i in 0..3000 {
+                graph.add_edge(black_box(i), i * 2 % 3000, i % 10);
+            }
+            for i in 0..3000 {
+                graph.add_edge(black_box(i), (i + 200) % 3000, 1usize);
+            }
+
+            for i in 0..3000 {
+                graph.min_dists_dial(i, 10);
+            }
+        })
+    });
+}
+
+criterion_group!(
+    benches,
+    bit_bench,
+    min_dists_bench,
+    edge_list_graph_min_dists_bench,
+    min_dists_radix_heap_bench,
+    min_dists_01_bench,
+    min_dists_dial_bench
+);
 criterion_main!(benches);
@@ -0,0 +1,290 @@
+use cargo_snippet::snippet;
+
+// 0以上2^bits未満の整数の多重集合を管理するTrie木。各クエリはO(bits)
+// xor_allで全要素にxorを作用させられる。各クエリの引数xorは、そのクエリの間だけ全要素にxorを作用させたものとして扱う
+#[snippet("@BinaryTrie")]
+pub struct BinaryTrie {
+    bits: usize,
+    // children[node][b]: nodeから見て次のbitがbである子
+    children: Vec<[Option<usize>; 2]>,
+    // counts[node]: nodeの部分木に含まれる要素数
+    counts: Vec<usize>,
+    mask: u64,
+}
+
+#[snippet("@BinaryTrie")]
+impl BinaryTrie {
+    // bits: 扱う整数のbit幅(64以下)
+    pub fn new(bits: usize) -> Self {
+        assert!(bits <= 64);
+        BinaryTrie {
+            bits,
+            children: vec![[None, None]],
+            counts: vec![0],
+            mask: 0,
+        }
+    }
+
+    // 2^bits以上の値は下位bitだけが使われて別の値になってしまうので弾く
+    fn assert_in_range(&self, value: u64) {
+        assert!(self.bits == 64 || value >> self.bits == 0);
+    }
+
+    fn count_of(&self, node: Option<usize>) -> usize {
+        node.map_or(0, |node| self.counts[node])
+    }
+
+    pub fn len(&self) -> usize {
+        self.counts[0]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // 全要素にxをxorする O(1)
+    pub fn xor_all(&mut self, x: u64) {
+        self.assert_in_range(x);
+        self.mask ^= x;
+    }
+
+    pub fn insert(&mut self, value: u64) {
+        self.assert_in_range(value);
+        let key = value ^ self.mask;
+        let mut node = 0;
+        self.counts[node] += 1;
+        for b in (0..self.bits).rev() {
+            let bit = ((key >> b) & 1) as usize;
+            node = match self.children[node][bit] {
+                Some(child) => child,
+                None => {
+                    self.children.push([None, None]);
+                    self.counts.push(0);
+                    let child = self.children.len() - 1;
+                    self.children[node][bit] = Some(child);
+                    child
+                }
+            };
+            self.counts[node] += 1;
+        }
+    }
+
+    // valueを1つ削除する。valueが含まれていなければfalse
+    pub fn erase(&mut self, value: u64) -> bool {
+        if self.count(value) == 0 {
+            return false;
+        }
+
+        let key = value ^ self.mask;
+        let mut node = 0;
+        self.counts[node] -= 1;
+        for b in (0..self.bits).rev() {
+            let bit = ((key >> b) & 1) as usize;
+            node = self.children[node][bit].unwrap();
+            self.counts[node] -= 1;
+        }
+
+        true
+    }
+
+    // valueの個数
+    pub fn count(&self, value: u64) -> usize {
+        self.assert_in_range(value);
+        let key = value ^ self.mask;
+        let mut node = Some(0);
+        for b in (0..self.bits).rev() {
+            let bit = ((key >> b) & 1) as usize;
+            node = match node {
+                Some(node) => self.children[node][bit],
+                None => return 0,
+            };
+        }
+
+        self.count_of(node)
+    }
+
+    // min(a ^ xor)
+    pub fn min_xor(&self, xor: u64) -> Option<u64> {
+        self.kth_smallest(0, xor)
+    }
+
+    // max(a ^ xor)
+    pub fn max_xor(&self, xor: u64) -> Option<u64> {
+        self.assert_in_range(xor);
+        if self.is_empty() {
+            return None;
+        }
+
+        self.kth_smallest(self.len() - 1, xor)
+    }
+
+    // a ^ xorを小さい順に並べたときのk番目(0-indexed)
+    pub fn kth_smallest(&self, mut k: usize, xor: u64) -> Option<u64> {
+        self.assert_in_range(xor);
+        if k >= self.len() {
+            return None;
+        }
+
+        let key = xor ^ self.mask;
+        let mut node = 0;
+        let mut result = 0;
+        for b in (0..self.bits).rev() {
+            let bit = ((key >> b) & 1) as usize;
+            let zero_side_count = self.count_of(self.children[node][bit]);
+            if k < zero_side_count {
+                node = self.children[node][bit].unwrap();
+            } else {
+                k -= zero_side_count;
+                node = self.children[node][bit ^ 1].unwrap();
+                result |= 1 << b;
+            }
+        }
+
+        Some(result)
+    }
+
+    // a ^ xor < boundとなるaの個数
+    pub fn count_less(&self, bound: u64, xor: u64) -> usize {
+        self.assert_in_range(xor);
+        if self.bits < 64 && bound >> self.bits != 0 {
+            return self.len();
+        }
+
+        let key = xor ^ self.mask;
+        let mut node = 0;
+        let mut result = 0;
+        for b in (0..self.bits).rev() {
+            let bit = ((key >> b) & 1) as usize;
+            let next = if (bound >> b) & 1 == 1 {
+                result += self.count_of(self.children[node][bit]);
+                self.children[node][bit ^ 1]
+            } else {
+                self.children[node][bit]
+            };
+
+            match next {
+                Some(next) => node = next,
+                None => return result,
+            }
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn binary_trie_test() {
+        let mut trie = BinaryTrie::new(4);
+        trie.insert(3);
+        trie.insert(5);
+        trie.insert(5);
+        trie.insert(12);
+
+        assert_eq!(trie.len(), 4);
+        assert_eq!(trie.count(5), 2);
+        assert_eq!(trie.min_xor(0), Some(3));
+        assert_eq!(trie.max_xor(0), Some(12));
+        assert_eq!(trie.min_xor(4), Some(1));
+        assert_eq!(trie.max_xor(4), Some(8));
+        assert_eq!(trie.kth_smallest(1, 0), Some(5));
+        assert_eq!(trie.kth_smallest(2, 0), Some(5));
+        assert_eq!(trie.kth_smallest(4, 0), None);
+        assert_eq!(trie.count_less(5, 0), 1);
+        assert_eq!(trie.count_less(6, 0), 3);
+        assert_eq!(trie.count_less(16, 0), 4);
+
+        assert!(trie.erase(5));
+        assert!(!trie.erase(6));
+        assert_eq!(trie.count(5), 1);
+
+        // 3,5,12 -> 2,4,13
+        trie.xor_all(1);
+        assert_eq!(trie.count(4), 1);
+        assert_eq!(trie.count(5), 0);
+        assert_eq!(trie.kth_smallest(0, 0), Some(2));
+        assert_eq!(trie.kth_smallest(2, 0), Some(13));
+        assert_eq!(trie.count_less(5, 0), 2);
+
+        trie.insert(0);
+        assert_eq!(trie.min_xor(0), Some(0));
+        assert!(trie.erase(13));
+        assert_eq!(trie.max_xor(0), Some(4));
+
+        let mut trie = BinaryTrie::new(64);
+        trie.insert(u64::MAX);
+        trie.insert(1);
+        assert_eq!(trie.max_xor(0), Some(u64::MAX));
+        assert_eq!(trie.max_xor(1), Some(u64::MAX - 1));
+        assert_eq!(trie.count_less(u64::MAX, 0), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn binary_trie_insert_out_of_range_test() {
+        let mut trie = BinaryTrie::new(4);
+        trie.insert(16);
+    }
+
+    #[test]
+    #[should_panic]
+    fn binary_trie_xor_out_of_range_test() {
+        let mut trie = BinaryTrie::new(4);
+        trie.insert(1);
+        trie.count_less(3, 16);
+    }
+
+    proptest! {
+        #[test]
+        fn binary_trie_random_test(a :u8) {
+            let mut trie = BinaryTrie::new(10);
+            let mut values: Vec<u64> = vec![];
+            let mut mask = 0;
+
+            for _ in 0..a {
+                let value = rand::random::<u64>() % 1024;
+                match rand::random::<usize>() % 4 {
+                    0 => {
+                        let erased = trie.erase(value);
+                        let position = values.iter().position(|&v| v ^ mask == value);
+                        prop_assert_eq!(erased, position.is_some());
+                        if let Some(position) = position {
+                            values.remove(position);
+                        }
+                    }
+                    1 => {
+                        trie.xor_all(value);
+                        mask ^= value;
+                    }
+                    _ => {
+                        trie.insert(value);
+                        values.push(value ^ mask);
+                    }
+                }
+
+                let xor = rand::random::<u64>() % 1024;
+                let mut sorted: Vec<u64> = values.iter().map(|&v| v ^ mask ^ xor).collect();
+                sorted.sort();
+
+                prop_assert_eq!(trie.len(), sorted.len());
+                prop_assert_eq!(trie.min_xor(xor), sorted.first().copied());
+                prop_assert_eq!(trie.max_xor(xor), sorted.last().copied());
+                let k = rand::random::<usize>() % (sorted.len() + 1);
+                prop_assert_eq!(trie.kth_smallest(k, xor), sorted.get(k).copied());
+                let bound = rand::random::<u64>() % 1100;
+                prop_assert_eq!(
+                    trie.count_less(bound, xor),
+                    sorted.iter().filter(|&&v| v < bound).count()
+                );
+                prop_assert_eq!(
+                    trie.count(value),
+                    values.iter().filter(|&&v| v ^ mask == value).count()
+                );
+            }
+        }
+    }
+}
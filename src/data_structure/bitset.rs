@@ -0,0 +1,457 @@
+use cargo_snippet::snippet;
+
+// 長さnのbit列。シフトやbit演算をu64単位でまとめて行うので、Vec<bool>の約64倍高速に動作する
+// 長さの異なるBitset同士の演算はできない。シフトであふれたbitは捨てられる
+#[snippet("@Bitset")]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Bitset {
+    len: usize,
+    words: Vec<u64>,
+}
+
+#[snippet("@Bitset")]
+impl Bitset {
+    pub fn new(len: usize) -> Self {
+        Bitset {
+            len,
+            words: vec![0; (len + 63) >> 6],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // 末尾のワードのうち、長さを超える部分のbitを0にする
+    fn trim(&mut self) {
+        let rest = self.len % 64;
+        if rest != 0 {
+            let last = self.words.len() - 1;
+            self.words[last] &= (1 << rest) - 1;
+        }
+    }
+
+    pub fn get(&self, index: usize) -> bool {
+        assert!(index < self.len);
+        (self.words[index / 64] >> (index % 64)) & 1 == 1
+    }
+
+    pub fn set(&mut self, index: usize, value: bool) {
+        assert!(index < self.len);
+        if value {
+            self.words[index / 64] |= 1 << (index % 64);
+        } else {
+            self.words[index / 64] &= !(1 << (index % 64));
+        }
+    }
+
+    pub fn flip(&mut self, index: usize) {
+        assert!(index < self.len);
+        self.words[index / 64] ^= 1 << (index % 64);
+    }
+
+    pub fn set_all(&mut self) {
+        self.words.iter_mut().for_each(|word| *word = u64::MAX);
+        self.trim();
+    }
+
+    pub fn reset_all(&mut self) {
+        self.words.iter_mut().for_each(|word| *word = 0);
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    // 立っているbitのうち最小の位置
+    pub fn find_first(&self) -> Option<usize> {
+        self.find_from(0)
+    }
+
+    // 立っているbitのうち、indexより大きい最小の位置
+    pub fn find_next(&self, index: usize) -> Option<usize> {
+        self.find_from(index.checked_add(1)?)
+    }
+
+    // 立っているbitのうち、index以上の最小の位置
+    fn find_from(&self, index: usize) -> Option<usize> {
+        if index >= self.len {
+            return None;
+        }
+
+        let mut word_index = index / 64;
+        let mut word = self.words[word_index] & (u64::MAX << (index % 64));
+        loop {
+            if word != 0 {
+                return Some(word_index * 64 + word.trailing_zeros() as usize);
+            }
+            word_index += 1;
+            if word_index == self.words.len() {
+                return None;
+            }
+            word = self.words[word_index];
+        }
+    }
+
+    // 立っているbitの位置を昇順に列挙する
+    pub fn iter_ones(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words
+            .iter()
+            .enumerate()
+            .flat_map(|(word_index, &word)| {
+                let mut word = word;
+                std::iter::from_fn(move || {
+                    if word == 0 {
+                        return None;
+                    }
+                    let bit = word.trailing_zeros() as usize;
+                    word &= word - 1;
+                    Some(word_index * 64 + bit)
+                })
+            })
+    }
+}
+
+#[snippet("@Bitset")]
+impl std::ops::ShlAssign<usize> for Bitset {
+    // i番目のbitをi+shift番目に移す
+    fn shl_assign(&mut self, shift: usize) {
+        let word_shift = shift / 64;
+        let bit_shift = shift % 64;
+        for i in (0..self.words.len()).rev() {
+            let mut word = 0;
+            if i >= word_shift {
+                word = self.words[i - word_shift] << bit_shift;
+                if bit_shift > 0 && i > word_shift {
+                    word |= self.words[i - word_shift - 1] >> (64 - bit_shift);
+                }
+            }
+            self.words[i] = word;
+        }
+        self.trim();
+    }
+}
+
+#[snippet("@Bitset")]
+impl std::ops::ShrAssign<usize> for Bitset {
+    // i番目のbitをi-shift番目に移す
+    fn shr_assign(&mut self, shift: usize) {
+        let word_shift = shift / 64;
+        let bit_shift = shift % 64;
+        let words_len = self.words.len();
+        for i in 0..words_len {
+            let mut word = 0;
+            if i + word_shift < words_len {
+                word = self.words[i + word_shift] >> bit_shift;
+                if bit_shift > 0 && i + word_shift + 1 < words_len {
+                    word |= self.words[i + word_shift + 1] << (64 - bit_shift);
+                }
+            }
+            self.words[i] = word;
+        }
+    }
+}
+
+#[snippet("@Bitset")]
+impl std::ops::Shl<usize> for &Bitset {
+    type Output = Bitset;
+    fn shl(self, shift: usize) -> Bitset {
+        let mut result = self.clone();
+        result <<= shift;
+        result
+    }
+}
+
+#[snippet("@Bitset")]
+impl std::ops::Shr<usize> for &Bitset {
+    type Output = Bitset;
+    fn shr(self, shift: usize) -> Bitset {
+        let mut result = self.clone();
+        result >>= shift;
+        result
+    }
+}
+
+#[snippet("@Bitset")]
+impl std::ops::Shl<usize> for Bitset {
+    type Output = Bitset;
+    fn shl(mut self, shift: usize) -> Bitset {
+        self <<= shift;
+        self
+    }
+}
+
+#[snippet("@Bitset")]
+impl std::ops::Shr<usize> for Bitset {
+    type Output = Bitset;
+    fn shr(mut self, shift: usize) -> Bitset {
+        self >>= shift;
+        self
+    }
+}
+
+#[snippet("@Bitset")]
+impl std::ops::BitAndAssign<&Bitset> for Bitset {
+    fn bitand_assign(&mut self, other: &Bitset) {
+        assert_eq!(self.len, other.len);
+        self.words
+            .iter_mut()
+            .zip(other.words.iter())
+            .for_each(|(word, &other_word)| *word &= other_word);
+    }
+}
+
+#[snippet("@Bitset")]
+impl std::ops::BitOrAssign<&Bitset> for Bitset {
+    fn bitor_assign(&mut self, other: &Bitset) {
+        assert_eq!(self.len, other.len);
+        self.words
+            .iter_mut()
+            .zip(other.words.iter())
+            .for_each(|(word, &other_word)| *word |= other_word);
+    }
+}
+
+#[snippet("@Bitset")]
+impl std::ops::BitXorAssign<&Bitset> for Bitset {
+    fn bitxor_assign(&mut self, other: &Bitset) {
+        assert_eq!(self.len, other.len);
+        self.words
+            .iter_mut()
+            .zip(other.words.iter())
+            .for_each(|(word, &other_word)| *word ^= other_word);
+    }
+}
+
+#[snippet("@Bitset")]
+impl std::ops::BitAnd for &Bitset {
+    type Output = Bitset;
+    fn bitand(self, other: &Bitset) -> Bitset {
+        let mut result = self.clone();
+        result &= other;
+        result
+    }
+}
+
+#[snippet("@Bitset")]
+impl std::ops::BitOr for &Bitset {
+    type Output = Bitset;
+    fn bitor(self, other: &Bitset) -> Bitset {
+        let mut result = self.clone();
+        result |= other;
+        result
+    }
+}
+
+#[snippet("@Bitset")]
+impl std::ops::BitXor for &Bitset {
+    type Output = Bitset;
+    fn bitxor(self, other: &Bitset) -> Bitset {
+        let mut result = self.clone();
+        result ^= other;
+        result
+    }
+}
+
+#[snippet("@Bitset")]
+impl std::ops::BitAndAssign<Bitset> for Bitset {
+    fn bitand_assign(&mut self, other: Bitset) {
+        *self &= &other;
+    }
+}
+
+#[snippet("@Bitset")]
+impl std::ops::BitAnd<&Bitset> for Bitset {
+    type Output = Bitset;
+    fn bitand(mut self, other: &Bitset) -> Bitset {
+        self &= other;
+        self
+    }
+}
+
+#[snippet("@Bitset")]
+impl std::ops::BitAnd for Bitset {
+    type Output = Bitset;
+    fn bitand(mut self, other: Bitset) -> Bitset {
+        self &= &other;
+        self
+    }
+}
+
+#[snippet("@Bitset")]
+impl std::ops::BitOrAssign<Bitset> for Bitset {
+    fn bitor_assign(&mut self, other: Bitset) {
+        *self |= &other;
+    }
+}
+
+#[snippet("@Bitset")]
+impl std::ops::BitOr<&Bitset> for Bitset {
+    type Output = Bitset;
+    fn bitor(mut self, other: &Bitset) -> Bitset {
+        self |= other;
+        self
+    }
+}
+
+#[snippet("@Bitset")]
+impl std::ops::BitOr for Bitset {
+    type Output = Bitset;
+    fn bitor(mut self, other: Bitset) -> Bitset {
+        self |= &other;
+        self
+    }
+}
+
+#[snippet("@Bitset")]
+impl std::ops::BitXorAssign<Bitset> for Bitset {
+    fn bitxor_assign(&mut self, other: Bitset) {
+        *self ^= &other;
+    }
+}
+
+#[snippet("@Bitset")]
+impl std::ops::BitXor<&Bitset> for Bitset {
+    type Output = Bitset;
+    fn bitxor(mut self, other: &Bitset) -> Bitset {
+        self ^= other;
+        self
+    }
+}
+
+#[snippet("@Bitset")]
+impl std::ops::BitXor for Bitset {
+    type Output = Bitset;
+    fn bitxor(mut self, other: Bitset) -> Bitset {
+        self ^= &other;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn bitset_test() {
+        let mut bitset = Bitset::new(100);
+        bitset.set(0, true);
+        bitset.set(3, true);
+        bitset.set(64, true);
+        bitset.set(99, true);
+
+        assert_eq!(bitset.count_ones(), 4);
+        assert!(bitset.get(64));
+        assert!(!bitset.get(65));
+        assert_eq!(bitset.find_first(), Some(0));
+        assert_eq!(bitset.find_next(0), Some(3));
+        assert_eq!(bitset.find_next(3), Some(64));
+        assert_eq!(bitset.find_next(64), Some(99));
+        assert_eq!(bitset.find_next(99), None);
+        assert_eq!(bitset.find_next(usize::MAX), None);
+        assert_eq!(bitset.iter_ones().collect::<Vec<_>>(), vec![0, 3, 64, 99]);
+
+        let shifted = &bitset << 1;
+        assert_eq!(shifted.iter_ones().collect::<Vec<_>>(), vec![1, 4, 65]);
+        let shifted = &bitset >> 3;
+        assert_eq!(shifted.iter_ones().collect::<Vec<_>>(), vec![0, 61, 96]);
+        let shifted = &bitset << 64;
+        assert_eq!(shifted.iter_ones().collect::<Vec<_>>(), vec![64, 67]);
+        let shifted = &bitset >> 200;
+        assert_eq!(shifted.count_ones(), 0);
+
+        let mut other = Bitset::new(100);
+        other.set(3, true);
+        other.set(50, true);
+        assert_eq!((&bitset & &other).iter_ones().collect::<Vec<_>>(), vec![3]);
+        assert_eq!(
+            (&bitset | &other).iter_ones().collect::<Vec<_>>(),
+            vec![0, 3, 50, 64, 99]
+        );
+        assert_eq!(
+            (&bitset ^ &other).iter_ones().collect::<Vec<_>>(),
+            vec![0, 50, 64, 99]
+        );
+
+        // 値を消費する演算
+        assert_eq!(
+            (bitset.clone() << 1).iter_ones().collect::<Vec<_>>(),
+            vec![1, 4, 65]
+        );
+        assert_eq!(
+            (bitset.clone() >> 3).iter_ones().collect::<Vec<_>>(),
+            vec![0, 61, 96]
+        );
+        assert_eq!(
+            (bitset.clone() & other.clone())
+                .iter_ones()
+                .collect::<Vec<_>>(),
+            vec![3]
+        );
+        assert_eq!(
+            (bitset.clone() | &other).iter_ones().collect::<Vec<_>>(),
+            vec![0, 3, 50, 64, 99]
+        );
+        let mut xor = bitset.clone();
+        xor ^= other.clone();
+        assert_eq!(xor, &bitset ^ &other);
+
+        bitset.flip(0);
+        bitset.set(99, false);
+        assert_eq!(bitset.iter_ones().collect::<Vec<_>>(), vec![3, 64]);
+
+        bitset.set_all();
+        assert_eq!(bitset.count_ones(), 100);
+        bitset.reset_all();
+        assert_eq!(bitset.find_first(), None);
+    }
+
+    #[test]
+    fn bitset_subset_sum_test() {
+        let values = vec![3, 5, 7];
+        let mut dp = Bitset::new(20);
+        dp.set(0, true);
+        for &value in &values {
+            dp |= &dp << value;
+        }
+
+        assert_eq!(
+            dp.iter_ones().collect::<Vec<_>>(),
+            vec![0, 3, 5, 7, 8, 10, 12, 15]
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn bitset_random_test(len :u8, shift :u8) {
+            let len = len as usize;
+            let shift = shift as usize;
+            let mut bitset = Bitset::new(len);
+            let mut naive = vec![false; len];
+            for i in 0..len {
+                if rand::random::<bool>() {
+                    bitset.set(i, true);
+                    naive[i] = true;
+                }
+            }
+
+            let ones: Vec<usize> = (0..len).filter(|&i| naive[i]).collect();
+            prop_assert_eq!(bitset.iter_ones().collect::<Vec<_>>(), ones.clone());
+            prop_assert_eq!(bitset.count_ones(), ones.len());
+            prop_assert_eq!(bitset.find_first(), ones.first().copied());
+
+            let shl_ones: Vec<usize> = ones.iter().map(|&i| i + shift).filter(|&i| i < len).collect();
+            prop_assert_eq!((&bitset << shift).iter_ones().collect::<Vec<_>>(), shl_ones);
+            let shr_ones: Vec<usize> = ones.iter().filter(|&&i| i >= shift).map(|&i| i - shift).collect();
+            prop_assert_eq!((&bitset >> shift).iter_ones().collect::<Vec<_>>(), shr_ones);
+        }
+    }
+}
@@ -0,0 +1,240 @@
+use cargo_snippet::snippet;
+
+// 併合可能なヒープ(最小値を取り出す)。meld,push,popはO(log n)
+// ノードはRcで共有しているので、cloneはO(1)で行え、clone元のヒープはその後の操作の影響を受けない(永続)
+#[snippet("@LeftistHeap")]
+#[derive(Clone, Debug)]
+struct LeftistHeapNode<T> {
+    value: T,
+    // 右の枝をたどって葉(None)に着くまでの距離
+    rank: usize,
+    left: Option<std::rc::Rc<LeftistHeapNode<T>>>,
+    right: Option<std::rc::Rc<LeftistHeapNode<T>>>,
+}
+
+#[snippet("@LeftistHeap")]
+#[derive(Clone, Debug)]
+pub struct LeftistHeap<T> {
+    root: Option<std::rc::Rc<LeftistHeapNode<T>>>,
+    len: usize,
+}
+
+#[snippet("@LeftistHeap")]
+impl<T: Ord + Clone> LeftistHeap<T> {
+    pub fn new() -> Self {
+        LeftistHeap { root: None, len: 0 }
+    }
+
+    fn rank(node: &Option<std::rc::Rc<LeftistHeapNode<T>>>) -> usize {
+        match node {
+            Some(node) => node.rank,
+            None => 0,
+        }
+    }
+
+    // 他のヒープと共有されているノードのみ複製する
+    fn take_node(node: std::rc::Rc<LeftistHeapNode<T>>) -> LeftistHeapNode<T> {
+        std::rc::Rc::try_unwrap(node).unwrap_or_else(|shared| (*shared).clone())
+    }
+
+    fn meld_node(
+        a: Option<std::rc::Rc<LeftistHeapNode<T>>>,
+        b: Option<std::rc::Rc<LeftistHeapNode<T>>>,
+    ) -> Option<std::rc::Rc<LeftistHeapNode<T>>> {
+        let (a, b) = match (a, b) {
+            (None, b) => return b,
+            (a, None) => return a,
+            (Some(a), Some(b)) => {
+                if a.value <= b.value {
+                    (a, b)
+                } else {
+                    (b, a)
+                }
+            }
+        };
+
+        let mut node = Self::take_node(a);
+        node.right = Self::meld_node(node.right.take(), Some(b));
+        if Self::rank(&node.left) < Self::rank(&node.right) {
+            std::mem::swap(&mut node.left, &mut node.right);
+        }
+        node.rank = Self::rank(&node.right) + 1;
+
+        Some(std::rc::Rc::new(node))
+    }
+
+    // otherの要素をすべて取り込む O(log n)
+    pub fn meld(&mut self, mut other: LeftistHeap<T>) {
+        self.root = Self::meld_node(self.root.take(), other.root.take());
+        self.len += other.len;
+    }
+
+    pub fn push(&mut self, value: T) {
+        let node = LeftistHeapNode {
+            value,
+            rank: 1,
+            left: None,
+            right: None,
+        };
+        self.root = Self::meld_node(self.root.take(), Some(std::rc::Rc::new(node)));
+        self.len += 1;
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.root.as_ref().map(|node| &node.value)
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        let root = self.root.take()?;
+        let node = Self::take_node(root);
+        self.root = Self::meld_node(node.left, node.right);
+        self.len -= 1;
+
+        Some(node.value)
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+#[snippet("@LeftistHeap")]
+impl<T: Ord + Clone> Default for LeftistHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// 左の枝が長いと再帰的な解放でスタックが溢れるので、明示的なスタックで解放する
+#[snippet("@LeftistHeap")]
+impl<T> Drop for LeftistHeap<T> {
+    fn drop(&mut self) {
+        let mut stack: Vec<_> = self.root.take().into_iter().collect();
+        while let Some(node) = stack.pop() {
+            // 他のヒープと共有されているノードは、そのヒープが解放する
+            if let Ok(mut node) = std::rc::Rc::try_unwrap(node) {
+                stack.extend(node.left.take());
+                stack.extend(node.right.take());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn leftist_heap_test() {
+        let mut heap = LeftistHeap::new();
+        heap.push(5);
+        heap.push(1);
+        heap.push(8);
+        heap.push(3);
+
+        assert_eq!(heap.len(), 4);
+        assert_eq!(heap.peek(), Some(&1));
+        assert_eq!(heap.pop(), Some(1));
+        assert_eq!(heap.pop(), Some(3));
+
+        let mut other = LeftistHeap::new();
+        other.push(4);
+        other.push(10);
+        other.push(2);
+        heap.meld(other);
+
+        assert_eq!(heap.len(), 5);
+        assert_eq!(heap.pop(), Some(2));
+        assert_eq!(heap.pop(), Some(4));
+        assert_eq!(heap.pop(), Some(5));
+        assert_eq!(heap.pop(), Some(8));
+        assert_eq!(heap.pop(), Some(10));
+        assert_eq!(heap.pop(), None);
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn leftist_heap_persistent_test() {
+        let mut heap = LeftistHeap::new();
+        for value in [7, 3, 9, 1] {
+            heap.push(value);
+        }
+
+        let snapshot = heap.clone();
+        heap.pop();
+        heap.push(0);
+
+        let mut melded = snapshot.clone();
+        melded.meld(heap.clone());
+
+        let mut heap_values = vec![];
+        while let Some(value) = heap.pop() {
+            heap_values.push(value);
+        }
+        let mut snapshot_values = vec![];
+        let mut snapshot_copy = snapshot.clone();
+        while let Some(value) = snapshot_copy.pop() {
+            snapshot_values.push(value);
+        }
+        let mut melded_values = vec![];
+        while let Some(value) = melded.pop() {
+            melded_values.push(value);
+        }
+
+        assert_eq!(heap_values, vec![0, 3, 7, 9]);
+        assert_eq!(snapshot_values, vec![1, 3, 7, 9]);
+        assert_eq!(melded_values, vec![0, 1, 3, 3, 7, 7, 9, 9]);
+        assert_eq!(snapshot.peek(), Some(&1));
+        assert_eq!(snapshot.len(), 4);
+    }
+
+    #[test]
+    fn leftist_heap_drop_test() {
+        // 降順に入れると左の枝が一直線に伸びる
+        let mut heap = LeftistHeap::new();
+        for value in (0..1_000_000).rev() {
+            heap.push(value);
+        }
+        let snapshot = heap.clone();
+        assert_eq!(heap.pop(), Some(0));
+        drop(heap);
+        assert_eq!(snapshot.len(), 1_000_000);
+        drop(snapshot);
+    }
+
+    proptest! {
+        #[test]
+        fn leftist_heap_random_test(a in prop::collection::vec(0..1000i32, 0..100), b in prop::collection::vec(0..1000i32, 0..100)) {
+            let mut heap_a = LeftistHeap::new();
+            a.iter().for_each(|&x| heap_a.push(x));
+            let mut heap_b = LeftistHeap::new();
+            b.iter().for_each(|&x| heap_b.push(x));
+
+            let before_meld = heap_a.clone();
+            heap_a.meld(heap_b);
+
+            let mut expected = [a.clone(), b].concat();
+            expected.sort();
+
+            let mut result = vec![];
+            while let Some(x) = heap_a.pop() {
+                result.push(x);
+            }
+            prop_assert_eq!(result, expected);
+
+            let mut expected = a;
+            expected.sort();
+            let mut before_meld = before_meld;
+            let mut result = vec![];
+            while let Some(x) = before_meld.pop() {
+                result.push(x);
+            }
+            prop_assert_eq!(result, expected);
+        }
+    }
+}
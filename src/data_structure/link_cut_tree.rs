@@ -0,0 +1,400 @@
+use cargo_snippet::snippet;
+
+// 森に対して辺の追加・削除、パス上の値の集約・更新を行う。各操作はならしO(log n)
+// 頂点は0-indexed。根はevert,link,cut,query_path,apply_pathによって変化する
+#[snippet("@LinkCutTree")]
+pub struct LinkCutTree<T: std::clone::Clone, U: std::clone::Clone> {
+    // operationはf(a,f(b,c)) = f(f(a,b),c) を満たす必要がある(可換でなくてもよい)
+    // パスに対するクエリの処理
+    operation: fn(T, T) -> T,
+
+    // パス更新時に各頂点(の集約値)に対して行う処理
+    // effector(operation(a,b),c) = operation(effector(a,c),effector(b,c))を満たす必要がある
+    effector: fn(T, U) -> T,
+
+    // パスに対する作用が重なった時の解決処理
+    resolve_effect: fn(U, U) -> U,
+
+    left: Vec<Option<usize>>,
+    right: Vec<Option<usize>>,
+    parent: Vec<Option<usize>>,
+    values: Vec<T>,
+    // sums[x]: xを根とするsplay木を左から順に集約した値、rev_sums[x]: 右から順に集約した値
+    sums: Vec<T>,
+    rev_sums: Vec<T>,
+    lazy: Vec<Option<U>>,
+    reversed: Vec<bool>,
+}
+
+#[snippet("@LinkCutTree")]
+impl<T: std::clone::Clone, U: std::clone::Clone> LinkCutTree<T, U> {
+    // 各頂点がそれぞれ独立した木である森を作る
+    pub fn new(
+        values: Vec<T>,
+        operation: fn(T, T) -> T,
+        effector: fn(T, U) -> T,
+        resolve_effect: fn(U, U) -> U,
+    ) -> LinkCutTree<T, U> {
+        let n = values.len();
+        LinkCutTree {
+            operation,
+            effector,
+            resolve_effect,
+            left: vec![None; n],
+            right: vec![None; n],
+            parent: vec![None; n],
+            sums: values.clone(),
+            rev_sums: values.clone(),
+            values,
+            lazy: vec![None; n],
+            reversed: vec![false; n],
+        }
+    }
+
+    // xがsplay木の根かどうか
+    fn is_root(&self, x: usize) -> bool {
+        match self.parent[x] {
+            None => true,
+            Some(p) => self.left[p] != Some(x) && self.right[p] != Some(x),
+        }
+    }
+
+    fn update(&mut self, x: usize) {
+        let operation = self.operation;
+        let mut sum = self.values[x].clone();
+        let mut rev_sum = self.values[x].clone();
+        if let Some(l) = self.left[x] {
+            sum = operation(self.sums[l].clone(), sum);
+            rev_sum = operation(rev_sum, self.rev_sums[l].clone());
+        }
+        if let Some(r) = self.right[x] {
+            sum = operation(sum, self.sums[r].clone());
+            rev_sum = operation(self.rev_sums[r].clone(), rev_sum);
+        }
+        self.sums[x] = sum;
+        self.rev_sums[x] = rev_sum;
+    }
+
+    // xを根とするsplay木を左右反転する
+    fn toggle(&mut self, x: usize) {
+        let (left, right) = (self.left[x], self.right[x]);
+        self.left[x] = right;
+        self.right[x] = left;
+        std::mem::swap(&mut self.sums[x], &mut self.rev_sums[x]);
+        self.reversed[x] = !self.reversed[x];
+    }
+
+    fn all_apply(&mut self, x: usize, effect: U) {
+        let effector = self.effector;
+        self.values[x] = effector(self.values[x].clone(), effect.clone());
+        self.sums[x] = effector(self.sums[x].clone(), effect.clone());
+        self.rev_sums[x] = effector(self.rev_sums[x].clone(), effect.clone());
+        self.lazy[x] = match self.lazy[x].take() {
+            Some(origin_effect) => Some((self.resolve_effect)(origin_effect, effect)),
+            None => Some(effect),
+        };
+    }
+
+    fn push(&mut self, x: usize) {
+        if self.reversed[x] {
+            if let Some(l) = self.left[x] {
+                self.toggle(l);
+            }
+            if let Some(r) = self.right[x] {
+                self.toggle(r);
+            }
+            self.reversed[x] = false;
+        }
+        if let Some(effect) = self.lazy[x].take() {
+            if let Some(l) = self.left[x] {
+                self.all_apply(l, effect.clone());
+            }
+            if let Some(r) = self.right[x] {
+                self.all_apply(r, effect);
+            }
+        }
+    }
+
+    fn rotate(&mut self, x: usize) {
+        let p = self.parent[x].unwrap();
+        let g = self.parent[p];
+        if self.left[p] == Some(x) {
+            let b = self.right[x];
+            self.left[p] = b;
+            if let Some(b) = b {
+                self.parent[b] = Some(p);
+            }
+            self.right[x] = Some(p);
+        } else {
+            let b = self.left[x];
+            self.right[p] = b;
+            if let Some(b) = b {
+                self.parent[b] = Some(p);
+            }
+            self.left[x] = Some(p);
+        }
+        self.parent[p] = Some(x);
+        self.parent[x] = g;
+        if let Some(g) = g {
+            if self.left[g] == Some(p) {
+                self.left[g] = Some(x);
+            } else if self.right[g] == Some(p) {
+                self.right[g] = Some(x);
+            }
+        }
+        self.update(p);
+        self.update(x);
+    }
+
+    fn splay(&mut self, x: usize) {
+        // 上から順に遅延している処理を反映する
+        let mut path = vec![x];
+        let mut y = x;
+        while !self.is_root(y) {
+            y = self.parent[y].unwrap();
+            path.push(y);
+        }
+        while let Some(y) = path.pop() {
+            self.push(y);
+        }
+
+        while !self.is_root(x) {
+            let p = self.parent[x].unwrap();
+            if !self.is_root(p) {
+                let g = self.parent[p].unwrap();
+                if (self.left[g] == Some(p)) == (self.left[p] == Some(x)) {
+                    self.rotate(p);
+                } else {
+                    self.rotate(x);
+                }
+            }
+            self.rotate(x);
+        }
+    }
+
+    // 根からxまでのパスを1つのsplay木にし、xをその根にする。最後に切り替えた頂点を返す
+    fn access(&mut self, x: usize) -> usize {
+        let mut last = None;
+        let mut current = Some(x);
+        let mut result = x;
+        while let Some(c) = current {
+            self.splay(c);
+            self.right[c] = last;
+            self.update(c);
+            result = c;
+            last = Some(c);
+            current = self.parent[c];
+        }
+        self.splay(x);
+
+        result
+    }
+
+    // xを木の根にする
+    pub fn evert(&mut self, x: usize) {
+        self.access(x);
+        self.toggle(x);
+        self.push(x);
+    }
+
+    // xを含む木の根
+    pub fn root(&mut self, x: usize) -> usize {
+        self.access(x);
+        let mut y = x;
+        loop {
+            self.push(y);
+            match self.left[y] {
+                Some(l) => y = l,
+                None => break,
+            }
+        }
+        self.splay(y);
+
+        y
+    }
+
+    pub fn connected(&mut self, u: usize, v: usize) -> bool {
+        self.root(u) == self.root(v)
+    }
+
+    // 辺u-vを追加する。u,vは異なる木に属している必要がある
+    pub fn link(&mut self, u: usize, v: usize) {
+        assert!(!self.connected(u, v));
+        self.evert(u);
+        self.parent[u] = Some(v);
+    }
+
+    // 辺u-vを削除する。辺u-vが存在する必要がある
+    pub fn cut(&mut self, u: usize, v: usize) {
+        self.evert(u);
+        self.access(v);
+        self.push(u);
+        assert!(self.left[v] == Some(u) && self.right[u].is_none());
+        self.left[v] = None;
+        self.parent[u] = None;
+        self.update(v);
+    }
+
+    // 現在の根に対するu,vの最小共通祖先。u,vが異なる木に属していればNone
+    pub fn lca(&mut self, u: usize, v: usize) -> Option<usize> {
+        if !self.connected(u, v) {
+            return None;
+        }
+        self.access(u);
+
+        Some(self.access(v))
+    }
+
+    pub fn get(&mut self, x: usize) -> T {
+        self.access(x);
+        self.values[x].clone()
+    }
+
+    pub fn set(&mut self, x: usize, value: T) {
+        self.access(x);
+        self.values[x] = value;
+        self.update(x);
+    }
+
+    // uからvへのパス上の頂点の値を、uに近い順にoperationで集約する。u,vは同じ木に属している必要がある
+    pub fn query_path(&mut self, u: usize, v: usize) -> T {
+        assert!(self.connected(u, v));
+        self.evert(u);
+        self.access(v);
+        self.sums[v].clone()
+    }
+
+    // uからvへのパス上の頂点すべてにeffectを作用させる。u,vは同じ木に属している必要がある
+    pub fn apply_path(&mut self, u: usize, v: usize, effect: U) {
+        assert!(self.connected(u, v));
+        self.evert(u);
+        self.access(v);
+        self.all_apply(v, effect);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn link_cut_tree_test() {
+        let mut tree = LinkCutTree::<usize, usize>::new(
+            vec![1, 2, 3, 4, 5, 6],
+            |a, b| std::cmp::max(a, b),
+            |target, effect| target + effect,
+            |effect1, effect2| effect1 + effect2,
+        );
+
+        tree.link(0, 1);
+        tree.link(1, 2);
+        tree.link(1, 3);
+        tree.link(4, 5);
+
+        assert!(tree.connected(0, 3));
+        assert!(!tree.connected(0, 4));
+        assert_eq!(tree.query_path(0, 2), 3);
+        assert_eq!(tree.query_path(3, 0), 4);
+
+        tree.evert(0);
+        assert_eq!(tree.lca(2, 3), Some(1));
+        assert_eq!(tree.lca(0, 3), Some(0));
+        assert_eq!(tree.lca(2, 5), None);
+
+        tree.apply_path(0, 2, 10);
+        assert_eq!(tree.get(1), 12);
+        assert_eq!(tree.get(3), 4);
+        assert_eq!(tree.query_path(3, 0), 12);
+
+        tree.cut(1, 2);
+        assert!(!tree.connected(0, 2));
+        tree.link(2, 5);
+        assert!(tree.connected(2, 4));
+        assert_eq!(tree.query_path(2, 4), 13);
+
+        tree.set(4, 100);
+        assert_eq!(tree.query_path(4, 2), 100);
+        assert_eq!(tree.root(2), 4);
+    }
+
+    fn naive_path(adjacent: &[Vec<usize>], u: usize, v: usize) -> Option<Vec<usize>> {
+        let mut before = vec![None; adjacent.len()];
+        before[u] = Some(u);
+        let mut que = std::collections::VecDeque::new();
+        que.push_back(u);
+        while let Some(now) = que.pop_front() {
+            for &next in &adjacent[now] {
+                if before[next].is_none() {
+                    before[next] = Some(now);
+                    que.push_back(next);
+                }
+            }
+        }
+
+        before[v]?;
+        let mut path = vec![v];
+        let mut now = v;
+        while now != u {
+            now = before[now].unwrap();
+            path.push(now);
+        }
+        path.reverse();
+
+        Some(path)
+    }
+
+    proptest! {
+        #[test]
+        fn link_cut_tree_random_test(a :u8) {
+            let n = 12;
+            // 頂点の値をパス上で連結すると、uからvへのパスの頂点列になる
+            let mut tree = LinkCutTree::<Vec<usize>, ()>::new(
+                (0..n).map(|v| vec![v]).collect(),
+                |a, b| [a, b].concat(),
+                |target, _| target,
+                |_, _| (),
+            );
+            let mut adjacent = vec![vec![]; n];
+
+            for _ in 0..a {
+                let u = rand::random::<usize>() % n;
+                let v = rand::random::<usize>() % n;
+                let path = naive_path(&adjacent, u, v);
+
+                prop_assert_eq!(tree.connected(u, v), path.is_some());
+                match path {
+                    None => {
+                        tree.link(u, v);
+                        adjacent[u].push(v);
+                        adjacent[v].push(u);
+                    }
+                    Some(path) => {
+                        prop_assert_eq!(tree.query_path(u, v), path.clone());
+                        if path.len() == 2 && rand::random::<bool>() {
+                            tree.cut(v, u);
+                            adjacent[u].retain(|&x| x != v);
+                            adjacent[v].retain(|&x| x != u);
+                        }
+                    }
+                }
+
+                let root = rand::random::<usize>() % n;
+                tree.evert(root);
+                let x = rand::random::<usize>() % n;
+                let y = rand::random::<usize>() % n;
+                match (naive_path(&adjacent, root, x), naive_path(&adjacent, root, y)) {
+                    (Some(px), Some(py)) => {
+                        let expected = px.iter().zip(py.iter()).take_while(|(a, b)| a == b).last().map(|(&a, _)| a);
+                        prop_assert_eq!(tree.lca(x, y), expected);
+                    }
+                    _ => {
+                        if naive_path(&adjacent, x, y).is_none() {
+                            prop_assert_eq!(tree.lca(x, y), None);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
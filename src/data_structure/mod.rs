@@ -1,7 +1,12 @@
+pub mod binary_trie;
 pub mod bit;
+pub mod bitset;
 pub mod double_ended_priority_que;
 pub mod index_set;
 pub mod lazy_seg_tree;
+pub mod leftist_heap;
+pub mod link_cut_tree;
+pub mod radix_heap;
 pub mod range_set;
 pub mod rc_list;
 pub mod rollback_union_find;
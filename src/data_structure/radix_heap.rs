@@ -0,0 +1,140 @@
+use cargo_snippet::snippet;
+
+// 単調な(最後に取り出したキー以上のキーしかpushしない)最小ヒープ。キーは符号なし整数
+// Dijkstra法のように取り出すキーが単調増加する場合に、BinaryHeapより高速に動作する
+#[snippet("@RadixHeap")]
+#[snippet("@Graph")]
+#[snippet("@SimpleGraph")]
+pub struct RadixHeap<K, V> {
+    // buckets[i]には、lastとのxorの最上位bitがi-1番目であるキーを持つ要素が入る(buckets[0]はlastと等しいキー)
+    buckets: Vec<Vec<(K, V)>>,
+    last: K,
+    len: usize,
+}
+
+#[snippet("@RadixHeap")]
+#[snippet("@Graph")]
+#[snippet("@SimpleGraph")]
+impl<K: num::PrimInt + num::Unsigned, V> RadixHeap<K, V> {
+    pub fn new() -> Self {
+        let bits = std::mem::size_of::<K>() * 8;
+        RadixHeap {
+            buckets: (0..=bits).map(|_| vec![]).collect(),
+            last: K::zero(),
+            len: 0,
+        }
+    }
+
+    fn bucket_index(&self, key: K) -> usize {
+        if key == self.last {
+            0
+        } else {
+            self.buckets.len() - 1 - (key ^ self.last).leading_zeros() as usize
+        }
+    }
+
+    // keyは最後にpopしたキー以上である必要がある
+    pub fn push(&mut self, key: K, value: V) {
+        debug_assert!(key >= self.last);
+        let index = self.bucket_index(key);
+        self.buckets[index].push((key, value));
+        self.len += 1;
+    }
+
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        if self.len == 0 {
+            return None;
+        }
+
+        if self.buckets[0].is_empty() {
+            let index = (1..self.buckets.len())
+                .find(|&i| !self.buckets[i].is_empty())
+                .unwrap();
+            let bucket = std::mem::take(&mut self.buckets[index]);
+            self.last = bucket.iter().map(|&(key, _)| key).min().unwrap();
+            for (key, value) in bucket {
+                let new_index = self.bucket_index(key);
+                self.buckets[new_index].push((key, value));
+            }
+        }
+
+        self.len -= 1;
+        self.buckets[0].pop()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+#[snippet("@RadixHeap")]
+#[snippet("@Graph")]
+#[snippet("@SimpleGraph")]
+impl<K: num::PrimInt + num::Unsigned, V> Default for RadixHeap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn radix_heap_test() {
+        let mut heap = RadixHeap::new();
+        heap.push(5u32, 'a');
+        heap.push(1u32, 'b');
+        heap.push(8u32, 'c');
+
+        assert_eq!(heap.len(), 3);
+        assert_eq!(heap.pop(), Some((1, 'b')));
+
+        heap.push(1, 'd');
+        heap.push(6, 'e');
+
+        assert_eq!(heap.pop(), Some((1, 'd')));
+        assert_eq!(heap.pop(), Some((5, 'a')));
+        assert_eq!(heap.pop(), Some((6, 'e')));
+        assert_eq!(heap.pop(), Some((8, 'c')));
+        assert_eq!(heap.pop(), None);
+        assert!(heap.is_empty());
+
+        let mut heap = RadixHeap::new();
+        heap.push(u64::MAX, 0);
+        heap.push(0u64, 1);
+        assert_eq!(heap.pop(), Some((0, 1)));
+        assert_eq!(heap.pop(), Some((u64::MAX, 0)));
+    }
+
+    proptest! {
+        #[test]
+        fn radix_heap_random_test(a :u8) {
+            let mut heap = RadixHeap::new();
+            let mut binary_heap = std::collections::BinaryHeap::new();
+
+            let mut last = 0usize;
+            for i in 0..(a as usize) {
+                let key = last + rand::random::<usize>() % 1000;
+                heap.push(key, i);
+                binary_heap.push(std::cmp::Reverse(key));
+
+                if i % 3 == 0 {
+                    let (popped, _) = heap.pop().unwrap();
+                    prop_assert_eq!(popped, binary_heap.pop().unwrap().0);
+                    last = popped;
+                }
+            }
+
+            while let Some(std::cmp::Reverse(key)) = binary_heap.pop() {
+                prop_assert_eq!(heap.pop().unwrap().0, key);
+            }
+            prop_assert!(heap.is_empty());
+        }
+    }
+}
@@ -1,6 +1,12 @@
-// TODO: ワーシャルフロイド
-
+use crate::data_structure::radix_heap::RadixHeap;
 use crate::data_structure::union_find::UnionFind;
+use crate::graph::csr::Csr;
+use crate::graph::distance::Distance;
+use crate::graph::lowlink::LowLink;
+use crate::graph::scc::scc_ids;
+use crate::graph::shortest_path_tree::ShortestPathTree;
+use crate::graph::tree::Tree;
+use crate::graph::warshall_floyd::WarshallFloyd;
 use cargo_snippet::snippet;
 
 #[snippet("@Graph")]
@@ -8,6 +14,8 @@ pub struct Graph<T> {
     size: usize,
     edges: Vec<(usize, usize, T)>,
     directed: bool,
+    // edgesから作る隣接リスト。最初に必要になった時に作り、辺の追加で破棄する
+    csr: std::cell::OnceCell<Csr<T>>,
 }
 
 #[snippet("@Graph")]
@@ -18,20 +26,50 @@ impl<T: std::marker::Copy + std::cmp::PartialOrd> Graph<T> {
             size: n,
             edges: vec![],
             directed,
+            csr: std::cell::OnceCell::new(),
+        }
+    }
+
+    // 隣接リスト。辺の追加後に最初に呼ばれた時のみO(n+m)で作り直す
+    pub fn csr(&self) -> &Csr<T> {
+        self.csr.get_or_init(|| Csr::new(self.size, &self.edges))
+    }
+
+    // add_edgeでid番目(0-indexed)に追加した辺(from, to, cost)
+    // mst, shortest_path_tree, lowlink, eulerian_trailが返す辺番号はこの番号
+    pub fn edge(&self, id: usize) -> (usize, usize, T) {
+        self.edges[self.edge_index(id)]
+    }
+
+    // 追加した順番の辺番号と、内部の辺リスト(無向グラフでは各辺を両方向に持つ)の添字の変換
+    fn edge_index(&self, id: usize) -> usize {
+        if self.directed {
+            id
+        } else {
+            2 * id
+        }
+    }
+
+    fn edge_id(&self, index: usize) -> usize {
+        if self.directed {
+            index
+        } else {
+            index / 2
         }
     }
 
     // nodeに隣接する頂点を列挙
     pub fn adjacent_nodes(&self, node: usize) -> Vec<usize> {
-        self.edges
+        self.csr()
+            .adjacent(node)
             .iter()
-            .filter(|&&(from, _, _)| from == node)
-            .map(|&(_, to, _)| to)
+            .map(|&(to, _)| to)
             .collect()
     }
 
     // 辺の追加 O(1)
     pub fn add_edge(&mut self, from: usize, to: usize, cost: T) {
+        self.csr.take();
         self.edges.push((from, to, cost));
 
         // 無向グラフなら、反対にも辺を張る
@@ -63,15 +101,12 @@ impl<T: std::marker::Copy + std::cmp::PartialOrd> Graph<T> {
             }
         });
 
-        let mut edges_from = vec![vec![]; self.size];
-        self.edges.iter().for_each(|&(from, to, cost)| {
-            edges_from[from].push((to, cost));
-        });
+        let csr = self.csr();
 
         while !isolated.is_empty() {
             let next = isolated.pop_back().unwrap();
             result.push(next);
-            for &(to, _) in &edges_from[next] {
+            for &(to, _) in csr.adjacent(next) {
                 degs[to] -= 1;
                 if degs[to] == isolate_deg {
                     isolated.push_front(to);
@@ -83,113 +118,9 @@ impl<T: std::marker::Copy + std::cmp::PartialOrd> Graph<T> {
     }
 
     /// return pair of (# of scc, scc id)
-    fn scc_ids(&self) -> (usize, Vec<usize>) {
-        // Compressed sparse row?
-        pub struct Csr {
-            start: Vec<usize>,
-            elist: Vec<usize>,
-        }
-
-        impl Csr {
-            pub fn new(n: usize, edges: &[Vec<usize>]) -> Self {
-                let mut e_count = 0;
-                edges.iter().for_each(|edges_f| {
-                    e_count += edges_f.len();
-                });
-
-                let mut csr = Csr {
-                    start: vec![0; n + 1],
-                    elist: vec![0; e_count],
-                };
-                for i in 0..edges.len() {
-                    let from = i;
-                    let edges_f = &edges[from];
-                    for _ in edges_f.iter() {
-                        csr.start[from + 1] += 1;
-                    }
-                }
-
-                for i in 1..=n {
-                    csr.start[i] += csr.start[i - 1];
-                }
-
-                let mut counter = csr.start.clone();
-                for i in 0..edges.len() {
-                    let edges_f = &edges[i];
-                    let from = i;
-                    for to in edges_f.iter() {
-                        csr.elist[counter[from]] = *to;
-                        counter[from] += 1;
-                    }
-                }
-
-                csr
-            }
-        }
-
-        struct _Env {
-            graph: Csr,
-            now_ord: usize,
-            group_num: usize,
-            visited: Vec<usize>,
-            low: Vec<usize>,
-            ord: Vec<Option<usize>>,
-            ids: Vec<usize>,
-        }
-
-        // pure_edges[from] = [to1,to2,to3]
-        let mut pure_edges: Vec<Vec<usize>> = vec![vec![]; self.size];
-        self.edges.iter().for_each(|&(from, to, _)| {
-            pure_edges[from].push(to);
-        });
-
-        let mut env = _Env {
-            graph: Csr::new(self.size, &pure_edges),
-            now_ord: 0,
-            group_num: 0,
-            visited: Vec::with_capacity(self.size),
-            low: vec![0; self.size],
-            ord: vec![None; self.size],
-            ids: vec![0; self.size],
-        };
-
-        fn dfs(v: usize, n: usize, env: &mut _Env) {
-            env.low[v] = env.now_ord;
-            env.ord[v] = Some(env.now_ord);
-            env.now_ord += 1;
-            env.visited.push(v);
-
-            for i in env.graph.start[v]..env.graph.start[v + 1] {
-                let to = env.graph.elist[i];
-                if let Some(x) = env.ord[to] {
-                    env.low[v] = std::cmp::min(env.low[v], x);
-                } else {
-                    dfs(to, n, env);
-                    env.low[v] = std::cmp::min(env.low[v], env.low[to]);
-                }
-            }
-            if env.low[v] == env.ord[v].unwrap() {
-                loop {
-                    let u = *env.visited.last().unwrap();
-                    env.visited.pop();
-                    env.ord[u] = Some(n);
-                    env.ids[u] = env.group_num;
-                    if u == v {
-                        break;
-                    }
-                }
-                env.group_num += 1;
-            }
-        }
-        for i in 0..self.size {
-            if env.ord[i].is_none() {
-                dfs(i, self.size, &mut env);
-            }
-        }
-        for x in env.ids.iter_mut() {
-            *x = env.group_num - 1 - *x;
-        }
-        (env.group_num, env.ids)
+    /// scc ids are in topological order: if u can reach v, scc_id[u] <= scc_id[v]
+    pub fn scc_ids(&self) -> (usize, Vec<usize>) {
+        scc_ids(self.csr())
     }
 
     pub fn scc(&self) -> Vec<Vec<usize>> {
@@ -218,16 +149,14 @@ impl<T: std::marker::Copy + std::cmp::PartialOrd> Graph<T> {
         let mut visited = vec![std::usize::MAX; self.size];
         visited[start] = start;
 
-        let mut edges_from = vec![vec![]; self.size];
-        self.edges.iter().for_each(|&(from, to, cost)| {
-            edges_from[from].push((to, cost));
-        });
+        let csr = self.csr();
 
         while !que.is_empty() {
             let v = que.pop_back().unwrap();
             visits.push(v);
 
-            let dead_end = edges_from[v]
+            let dead_end = csr
+                .adjacent(v)
                 .iter()
                 .all(|e| visited[e.0] != std::usize::MAX);
 
@@ -239,8 +168,7 @@ impl<T: std::marker::Copy + std::cmp::PartialOrd> Graph<T> {
                 continue;
             }
 
-            for i in 0..edges_from[v].len() {
-                let e = edges_from[v][i];
+            for &e in csr.adjacent(v) {
                 if visited[e.0] == std::usize::MAX {
                     visited[e.0] = v;
                     que.push_back(e.0);
@@ -252,6 +180,171 @@ impl<T: std::marker::Copy + std::cmp::PartialOrd> Graph<T> {
         visits
     }
 
+    // 全ての辺をちょうど1回ずつ通る路(オイラー路)を、(頂点列, 辺番号の列)で求める(Hierholzer法) O(n+m)
+    // 辺番号はadd_edgeで追加した順番(edgeと同じ)。無向グラフで通った向きは頂点列から分かる
+    // 始点は、次数の条件から決まればその頂点、閉路になるなら辺を持つ最小の頂点。辺がなければ空の列を返す
+    pub fn eulerian_trail(&self) -> Option<(Vec<usize>, Vec<usize>)> {
+        // 出次数 - 入次数。無向グラフでは次数
+        let mut degs = vec![0isize; self.size];
+        for &(from, to, _) in &self.edges {
+            degs[from] += 1;
+            if self.directed {
+                degs[to] -= 1;
+            }
+        }
+
+        let starts: Vec<usize> = if self.directed {
+            if degs.iter().any(|&deg| deg.abs() > 1) {
+                return None;
+            }
+            (0..self.size).filter(|&v| degs[v] == 1).collect()
+        } else {
+            (0..self.size).filter(|&v| degs[v] % 2 == 1).collect()
+        };
+
+        match starts.len() {
+            0 => self.eulerian_circuit(),
+            1 if self.directed => self.eulerian_trail_from(starts[0]),
+            2 if !self.directed => self.eulerian_trail_from(starts[0]),
+            _ => None,
+        }
+    }
+
+    // 全ての辺をちょうど1回ずつ通って始点に戻る閉路(オイラー閉路)。返り値はeulerian_trailと同じ O(n+m)
+    pub fn eulerian_circuit(&self) -> Option<(Vec<usize>, Vec<usize>)> {
+        let mut degs = vec![0isize; self.size];
+        for &(from, to, _) in &self.edges {
+            degs[from] += 1;
+            if self.directed {
+                degs[to] -= 1;
+            }
+        }
+        let balanced = degs.iter().all(|&deg| {
+            if self.directed {
+                deg == 0
+            } else {
+                deg % 2 == 0
+            }
+        });
+        if !balanced {
+            return None;
+        }
+
+        match self.edges.first() {
+            Some(_) => self.eulerian_trail_from(self.edges.iter().map(|e| e.0).min().unwrap()),
+            None => Some((vec![], vec![])),
+        }
+    }
+
+    // startから全ての辺を通る路を求める。辺が連結でなければNone
+    fn eulerian_trail_from(&self, start: usize) -> Option<(Vec<usize>, Vec<usize>)> {
+        let csr = self.csr();
+        let mut used = vec![false; self.edges.len()];
+        // nexts[v]: vから出る辺で、次に調べる辺の位置
+        let mut nexts = vec![0; self.size];
+        let mut vertices = Vec::with_capacity(self.edges.len() + 1);
+        let mut edge_path = Vec::with_capacity(self.edges.len());
+        // (頂点, その頂点に来るのに使った辺)
+        let mut stack = vec![(start, None)];
+
+        while let Some(&(v, _)) = stack.last() {
+            let edge_ids = csr.edge_ids(v);
+            while nexts[v] < edge_ids.len() && used[edge_ids[nexts[v]]] {
+                nexts[v] += 1;
+            }
+
+            if let Some(&edge_id) = edge_ids.get(nexts[v]) {
+                used[edge_id] = true;
+                // 無向グラフでは、反対向きの辺も使ったことにする
+                if !self.directed {
+                    used[edge_id ^ 1] = true;
+                }
+                stack.push((self.edges[edge_id].1, Some(edge_id)));
+            } else {
+                // 行き止まりになった頂点から順に確定する
+                let (v, edge_id) = stack.pop().unwrap();
+                vertices.push(v);
+                edge_path.extend(edge_id.map(|edge_id| self.edge_id(edge_id)));
+            }
+        }
+
+        let edge_count = if self.directed {
+            self.edges.len()
+        } else {
+            self.edges.len() / 2
+        };
+        if edge_path.len() != edge_count {
+            return None;
+        }
+
+        vertices.reverse();
+        edge_path.reverse();
+        Some((vertices, edge_path))
+    }
+
+    // 無向グラフを、隣接する頂点が異なる色になるように2色で塗る O(n+m)
+    // 塗れれば、連結成分ごとの(色0の頂点, 色1の頂点)を、成分の最小の頂点の順に返す。最小の頂点は色0になる
+    // 塗れなければ奇閉路を返す。[v0, v1, ..., vk-1]は、辺v0-v1, ..., vk-1-v0からなる閉路(kは奇数)
+    #[allow(clippy::type_complexity)]
+    pub fn bipartite_coloring(&self) -> Result<Vec<(Vec<usize>, Vec<usize>)>, Vec<usize>> {
+        assert!(!self.directed);
+        let csr = self.csr();
+        let mut component_ids = vec![usize::MAX; self.size];
+        let mut depths = vec![0; self.size];
+        let mut parents = vec![usize::MAX; self.size];
+        let mut count = 0;
+
+        for root in 0..self.size {
+            if component_ids[root] != usize::MAX {
+                continue;
+            }
+            component_ids[root] = count;
+            let mut queue = std::collections::VecDeque::new();
+            queue.push_back(root);
+
+            while let Some(v) = queue.pop_front() {
+                for &(to, _) in csr.adjacent(v) {
+                    if component_ids[to] == usize::MAX {
+                        component_ids[to] = count;
+                        depths[to] = depths[v] + 1;
+                        parents[to] = v;
+                        queue.push_back(to);
+                    } else if depths[to] % 2 == depths[v] % 2 {
+                        // BFS木でvとtoの共通の祖先まで上り、v -> lca -> toの閉路を作る
+                        let mut from_v = vec![v];
+                        let mut from_to = vec![to];
+                        let (mut a, mut b) = (v, to);
+                        while a != b {
+                            if depths[a] >= depths[b] {
+                                a = parents[a];
+                                from_v.push(a);
+                            } else {
+                                b = parents[b];
+                                from_to.push(b);
+                            }
+                        }
+                        from_to.pop();
+                        from_v.extend(from_to.into_iter().rev());
+                        return Err(from_v);
+                    }
+                }
+            }
+            count += 1;
+        }
+
+        let mut components = vec![(vec![], vec![]); count];
+        for v in 0..self.size {
+            let (zeros, ones) = &mut components[component_ids[v]];
+            if depths[v] % 2 == 0 {
+                zeros.push(v);
+            } else {
+                ones.push(v);
+            }
+        }
+
+        Ok(components)
+    }
+
     pub fn is_tree(&self) -> bool {
         if self.directed {
             return false;
@@ -268,10 +361,7 @@ impl<T: std::marker::Copy + std::cmp::PartialOrd> Graph<T> {
 
         let mut is_tree = true;
 
-        let mut edges_from = vec![vec![]; self.size];
-        self.edges.iter().for_each(|&(from, to, cost)| {
-            edges_from[from].push((to, cost));
-        });
+        let csr = self.csr();
 
         while !que.is_empty() {
             let (now, before) = que.pop_back().unwrap();
@@ -282,7 +372,7 @@ impl<T: std::marker::Copy + std::cmp::PartialOrd> Graph<T> {
                 visited[now] = true;
             }
 
-            edges_from[now].iter().for_each(|&(to, _)| {
+            csr.adjacent(now).iter().for_each(|&(to, _)| {
                 if to != before {
                     que.push_back((to, now));
                 }
@@ -299,6 +389,161 @@ impl<T: std::marker::Copy + std::cmp::PartialOrd> Graph<T> {
 
         return is_tree;
     }
+
+    // 無向グラフの最大マッチング(Edmondsの花分解) O(V^3)
+    // 返り値のmates[v]はvとマッチしている頂点。自己ループ辺は無視する
+    pub fn maximum_matching(&self) -> Vec<Option<usize>> {
+        assert!(!self.directed);
+
+        struct _Env<'a, T> {
+            graph: &'a Csr<T>,
+            mates: Vec<Option<usize>>,
+            // 交互木での親。マッチしていない辺で結ばれる
+            parents: Vec<Option<usize>>,
+            // 縮約した花の代表元
+            bases: Vec<usize>,
+            used: Vec<bool>,
+            in_blossom: Vec<bool>,
+        }
+
+        let mut env = _Env {
+            graph: self.csr(),
+            mates: vec![None; self.size],
+            parents: vec![None; self.size],
+            bases: (0..self.size).collect(),
+            used: vec![false; self.size],
+            in_blossom: vec![false; self.size],
+        };
+
+        // 交互木でのaとbの最も近い共通祖先(の花の代表元)
+        fn lca<T>(mut a: usize, mut b: usize, env: &_Env<T>) -> usize {
+            let mut visited = vec![false; env.bases.len()];
+            loop {
+                a = env.bases[a];
+                visited[a] = true;
+                match env.mates[a] {
+                    Some(mate) => a = env.parents[mate].unwrap(),
+                    None => break,
+                }
+            }
+            loop {
+                b = env.bases[b];
+                if visited[b] {
+                    return b;
+                }
+                b = env.parents[env.mates[b].unwrap()].unwrap();
+            }
+        }
+
+        // vから花の代表元baseまでの頂点を花に含め、親を付け替える
+        fn mark_path<T>(mut v: usize, base: usize, mut child: usize, env: &mut _Env<T>) {
+            while env.bases[v] != base {
+                let mate = env.mates[v].unwrap();
+                env.in_blossom[env.bases[v]] = true;
+                env.in_blossom[env.bases[mate]] = true;
+                env.parents[v] = Some(child);
+                child = mate;
+                v = env.parents[mate].unwrap();
+            }
+        }
+
+        // rootから始まる増加路の終点
+        fn find_path<T: Copy>(root: usize, env: &mut _Env<T>) -> Option<usize> {
+            let n = env.bases.len();
+            env.used.iter_mut().for_each(|used| *used = false);
+            env.parents.iter_mut().for_each(|parent| *parent = None);
+            for (v, base) in env.bases.iter_mut().enumerate() {
+                *base = v;
+            }
+
+            env.used[root] = true;
+            let mut queue = std::collections::VecDeque::new();
+            queue.push_back(root);
+            while let Some(v) = queue.pop_front() {
+                for &(to, _) in env.graph.adjacent(v) {
+                    if env.bases[v] == env.bases[to] || env.mates[v] == Some(to) {
+                        continue;
+                    }
+
+                    let is_outer =
+                        to == root || env.mates[to].and_then(|mate| env.parents[mate]).is_some();
+                    if is_outer {
+                        // 奇閉路が見つかったので花として縮約する
+                        let base = lca(v, to, env);
+                        env.in_blossom.iter_mut().for_each(|b| *b = false);
+                        mark_path(v, base, to, env);
+                        mark_path(to, base, v, env);
+                        for u in 0..n {
+                            if env.in_blossom[env.bases[u]] {
+                                env.bases[u] = base;
+                                if !env.used[u] {
+                                    env.used[u] = true;
+                                    queue.push_back(u);
+                                }
+                            }
+                        }
+                    } else if env.parents[to].is_none() {
+                        env.parents[to] = Some(v);
+                        match env.mates[to] {
+                            None => return Some(to),
+                            Some(mate) => {
+                                env.used[mate] = true;
+                                queue.push_back(mate);
+                            }
+                        }
+                    }
+                }
+            }
+
+            None
+        }
+
+        for root in 0..self.size {
+            if env.mates[root].is_some() {
+                continue;
+            }
+            // 増加路に沿ってマッチングを入れ替える
+            let mut v = find_path(root, &mut env);
+            while let Some(u) = v {
+                let parent = env.parents[u].unwrap();
+                let next = env.mates[parent];
+                env.mates[u] = Some(parent);
+                env.mates[parent] = Some(u);
+                v = next;
+            }
+        }
+
+        env.mates
+    }
+
+    // 無向グラフの木をrootを根とする根付き木にする O(n log n)
+    // 木でなければ(閉路がある・連結でない)panicする
+    pub fn tree(&self, root: usize) -> Tree<T>
+    where
+        T: num::Num,
+    {
+        assert!(!self.directed);
+        // n-1本の辺で連結なら木。連結かどうかはTree::from_csrで確かめる
+        assert_eq!(
+            self.edges.len() + 2,
+            2 * self.size,
+            "the graph is not a tree"
+        );
+        Tree::from_csr(self.csr(), root)
+    }
+
+    // 無向グラフの橋・関節点・二重連結成分 O(n+m)
+    // LowLinkでの辺の番号は、add_edgeで追加した順番(edgeと同じ)
+    pub fn lowlink(&self) -> LowLink {
+        assert!(!self.directed);
+        let edges: Vec<(usize, usize)> = self
+            .edges
+            .iter()
+            .step_by(2)
+            .map(|&(from, to, _)| (from, to))
+            .collect();
+        LowLink::new(self.size, &edges)
+    }
 }
 
 #[snippet("@Graph")]
@@ -311,10 +556,7 @@ impl<T: Copy + num::Unsigned + num::Bounded + num::Saturating + std::cmp::Ord> G
             std::collections::BinaryHeap::new();
         queue.push(std::cmp::Reverse((T::zero(), from)));
 
-        let mut edges_from = vec![vec![]; self.size];
-        self.edges.iter().for_each(|&(from, to, cost)| {
-            edges_from[from].push((to, cost));
-        });
+        let csr = self.csr();
 
         while !queue.is_empty() {
             let (cost, to) = queue.pop().unwrap().0;
@@ -322,7 +564,7 @@ impl<T: Copy + num::Unsigned + num::Bounded + num::Saturating + std::cmp::Ord> G
                 continue;
             }
 
-            for &(to_to, to_cost) in &edges_from[to] {
+            for &(to_to, to_cost) in csr.adjacent(to) {
                 let new_cost = cost.saturating_add(to_cost);
                 if from_to_n[to_to] > new_cost {
                     from_to_n[to_to] = new_cost;
@@ -334,14 +576,150 @@ impl<T: Copy + num::Unsigned + num::Bounded + num::Saturating + std::cmp::Ord> G
         from_to_n
     }
 
+    // 辺のコストが0か1のときの1対nの最小距離 O(V+E)
+    // 返り値の形式はmin_distsと同じ
+    pub fn min_dists_01(&self, from: usize) -> Vec<T> {
+        let mut from_to_n = vec![T::max_value(); self.size];
+        let mut queue = std::collections::VecDeque::new();
+        queue.push_back((T::zero(), from));
+
+        let csr = self.csr();
+
+        while let Some((cost, to)) = queue.pop_front() {
+            if from_to_n[to] < cost {
+                continue;
+            }
+
+            for &(to_to, to_cost) in csr.adjacent(to) {
+                assert!(to_cost <= T::one());
+                let new_cost = cost.saturating_add(to_cost);
+                if from_to_n[to_to] > new_cost {
+                    from_to_n[to_to] = new_cost;
+                    if to_cost == T::zero() {
+                        queue.push_front((new_cost, to_to));
+                    } else {
+                        queue.push_back((new_cost, to_to));
+                    }
+                }
+            }
+        }
+
+        from_to_n
+    }
+
     pub fn all_min_dists(&self) -> Vec<Vec<T>> {
         (0..self.size).map(|node| self.min_dists(node)).collect()
     }
 
+    // 木でのfromからの距離と、fromを根としたときの各頂点の親 O(n)
+    // min_distsと違い、dists[from]は0になる
+    fn tree_dists(&self, from: usize) -> (Vec<T>, Vec<Option<usize>>) {
+        let mut dists = vec![T::zero(); self.size];
+        let mut parents = vec![None; self.size];
+        let mut stack = vec![(from, usize::MAX)];
+
+        let csr = self.csr();
+
+        while let Some((v, parent)) = stack.pop() {
+            for &(to, cost) in csr.adjacent(v) {
+                if to != parent {
+                    dists[to] = dists[v].saturating_add(cost);
+                    parents[to] = Some(v);
+                    stack.push((to, v));
+                }
+            }
+        }
+
+        (dists, parents)
+    }
+
+    // 最も遠い頂点が複数あれば、番号の最も小さいもの
+    fn farthest(dists: &[T]) -> usize {
+        (0..dists.len()).rev().max_by_key(|&v| dists[v]).unwrap()
+    }
+
+    // 木の直径の長さと、その両端を結ぶパスの頂点列 O(n)
+    pub fn diameter(&self) -> (T, Vec<usize>) {
+        assert!(self.size > 0 && self.is_tree());
+        let (dists, _) = self.tree_dists(0);
+        let start = Graph::farthest(&dists);
+        let (dists, parents) = self.tree_dists(start);
+        let end = Graph::farthest(&dists);
+
+        let mut path = vec![end];
+        let mut v = end;
+        while let Some(parent) = parents[v] {
+            path.push(parent);
+            v = parent;
+        }
+        path.reverse();
+
+        (dists[end], path)
+    }
+
+    // 木の各頂点から最も遠い頂点までの距離(離心数) O(n)
+    // 最も遠い頂点は必ず直径の端点のどちらかになる
+    pub fn eccentricities(&self) -> Vec<T> {
+        let (_, path) = self.diameter();
+        let (start_dists, _) = self.tree_dists(path[0]);
+        let (end_dists, _) = self.tree_dists(*path.last().unwrap());
+
+        start_dists
+            .into_iter()
+            .zip(end_dists)
+            .map(|(a, b)| std::cmp::max(a, b))
+            .collect()
+    }
+
+    // 木の中心(離心数が最小の頂点)を昇順に列挙する O(n)
+    pub fn center(&self) -> Vec<usize> {
+        let eccentricities = self.eccentricities();
+        let min = *eccentricities.iter().min().unwrap();
+
+        (0..self.size)
+            .filter(|&v| eccentricities[v] == min)
+            .collect()
+    }
+
+    // min_distsと同じ最短距離に加えて、最短路で各頂点の1つ前の頂点と辺を求める
+    pub fn shortest_path_tree(&self, from: usize) -> ShortestPathTree<T> {
+        let mut from_to_n = vec![T::max_value(); self.size];
+        let mut prev_nodes = vec![None; self.size];
+        let mut prev_edges = vec![None; self.size];
+        let mut queue: std::collections::BinaryHeap<std::cmp::Reverse<(T, usize)>> =
+            std::collections::BinaryHeap::new();
+        queue.push(std::cmp::Reverse((T::zero(), from)));
+
+        let csr = self.csr();
+
+        while let Some(std::cmp::Reverse((cost, to))) = queue.pop() {
+            if from_to_n[to] < cost {
+                continue;
+            }
+
+            for (&(to_to, to_cost), &edge_id) in csr.adjacent(to).iter().zip(csr.edge_ids(to)) {
+                let new_cost = cost.saturating_add(to_cost);
+                if from_to_n[to_to] > new_cost {
+                    from_to_n[to_to] = new_cost;
+                    prev_nodes[to_to] = Some(to);
+                    prev_edges[to_to] = Some(self.edge_id(edge_id));
+                    queue.push(std::cmp::Reverse((new_cost, to_to)));
+                }
+            }
+        }
+
+        ShortestPathTree::new(from, from_to_n, prev_nodes, prev_edges)
+    }
+
+    // 最小全域木(森)に使う辺の番号を、コストの昇順に列挙する(Kruskal法)
+    // 辺番号はadd_edgeで追加した順番(edgeと同じ)
     pub fn mst(&self) -> Vec<usize> {
         let mut sorted_edges = vec![];
+        // 無向グラフでは、各辺の2つの向きのうち追加した向きだけを見る
+        let step = if self.directed { 1 } else { 2 };
         self.edges
             .iter()
+            .step_by(step)
             .enumerate()
             .for_each(|(e_index, &(from, to, cost))| sorted_edges.push((cost, from, to, e_index)));
         sorted_edges.sort();
@@ -361,79 +739,246 @@ impl<T: Copy + num::Unsigned + num::Bounded + num::Saturating + std::cmp::Ord> G
 }
 
 #[snippet("@Graph")]
-// 負辺を含むグラフのためのメソッド
-impl<T: Copy + num::Signed + num::Bounded + std::ops::AddAssign + std::cmp::Ord> Graph<T> {
-    // Bellman-Ford法で1対nの最小距離を求める。負閉路を検出した場合はNone
-    // このメソッドでは自己ループ辺を無視する。
-    pub fn min_dists_or_detect_negative_loop(&self, from: usize) -> Option<Vec<T>> {
+// 辺のコストが符号なし整数のグラフのためのメソッド
+impl<T: num::PrimInt + num::Unsigned> Graph<T> {
+    // min_distsと同じ結果をRadixHeapを用いたDijkstraで求める
+    pub fn min_dists_radix_heap(&self, from: usize) -> Vec<T> {
         let mut from_to_n = vec![T::max_value(); self.size];
-        from_to_n[from] = T::zero();
-        for _ in 0..self.size {
-            self.edges.iter().for_each(|&(from, to, cost)| {
-                if from_to_n[from] != T::max_value() {
-                    from_to_n[to] = std::cmp::min(from_to_n[to], from_to_n[from] + cost);
-                }
-            });
-        }
+        let mut queue = RadixHeap::new();
+        queue.push(T::zero(), from);
 
-        let mut dist_total_before: T = T::zero();
-
-        from_to_n
-            .iter()
-            .filter(|&&dist| dist != T::max_value())
-            .map(|dist| *dist)
-            .for_each(|x| {
-                dist_total_before += x;
-            });
+        let csr = self.csr();
 
-        // もう一周行う
-        self.edges.iter().for_each(|&(from, to, cost)| {
-            if from_to_n[from] != T::max_value() {
-                from_to_n[to] = std::cmp::min(from_to_n[to], from_to_n[from] + cost);
+        while let Some((cost, to)) = queue.pop() {
+            if from_to_n[to] < cost {
+                continue;
             }
-        });
 
-        let mut dist_total_after: T = T::zero();
+            for &(to_to, to_cost) in csr.adjacent(to) {
+                let new_cost = cost.saturating_add(to_cost);
+                if from_to_n[to_to] > new_cost {
+                    from_to_n[to_to] = new_cost;
+                    queue.push(new_cost, to_to);
+                }
+            }
+        }
 
         from_to_n
-            .iter()
-            .filter(|&&dist| dist != T::max_value())
-            .map(|dist| *dist)
-            .for_each(|x| {
-                dist_total_after += x;
-            });
+    }
 
-        if dist_total_before != dist_total_after {
-            None
-        } else {
-            Some(from_to_n)
+    // Dial法で1対nの最小距離を求める。辺のコストはmax_cost以下 O(V*max_cost+E)
+    // 距離をmax_cost+1個のバケットで循環的に管理する。返り値の形式はmin_distsと同じ
+    pub fn min_dists_dial(&self, from: usize, max_cost: usize) -> Vec<T> {
+        let mut from_to_n = vec![T::max_value(); self.size];
+        let mut buckets = vec![vec![]; max_cost + 1];
+        buckets[0].push(from);
+        let mut rest = 1;
+
+        let csr = self.csr();
+
+        let mut dist = 0;
+        while rest > 0 {
+            while let Some(to) = buckets[dist % (max_cost + 1)].pop() {
+                rest -= 1;
+                let cost = T::from(dist).unwrap();
+                if from_to_n[to] < cost {
+                    continue;
+                }
+
+                for &(to_to, to_cost) in csr.adjacent(to) {
+                    assert!(to_cost.to_usize().unwrap() <= max_cost);
+                    let new_cost = cost + to_cost;
+                    if from_to_n[to_to] > new_cost {
+                        from_to_n[to_to] = new_cost;
+                        buckets[new_cost.to_usize().unwrap() % (max_cost + 1)].push(to_to);
+                        rest += 1;
+                    }
+                }
+            }
+            dist += 1;
         }
+
+        from_to_n
     }
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use proptest::prelude::*;
-    use rand::prelude::*;
-
-    #[test]
-    fn add_edge_directed_graph_test() {
-        let mut graph = Graph::<usize>::new(5, true);
-        graph.add_edge(1, 0, 1);
-        graph.add_edge(1, 2, 1);
-        graph.add_edge(1, 3, 1);
-        graph.add_edge(1, 4, 1);
+#[snippet("@Graph")]
+// 負辺を含むグラフのためのメソッド
+impl<T: Copy + num::Signed + num::Bounded + std::ops::AddAssign + std::cmp::Ord> Graph<T> {
+    // Bellman-Ford法で1対nの最小距離を求める。負閉路を検出した場合はNone
+    // 負閉路の判定はbellman_fordと同じ
+    pub fn min_dists_or_detect_negative_loop(&self, from: usize) -> Option<Vec<T>> {
+        let mut from_to_n = vec![T::max_value(); self.size];
+        for (to, dist) in self.bellman_ford(from).into_iter().enumerate() {
+            match dist {
+                Distance::Finite(dist) => from_to_n[to] = dist,
+                Distance::Unreachable => {}
+                Distance::NegativeInfinity => return None,
+            }
+        }
 
-        assert_eq!(graph.adjacent_nodes(1), vec![0, 2, 3, 4]);
-        assert_eq!(graph.adjacent_nodes(0), vec![]);
-        assert_eq!(graph.adjacent_nodes(2), vec![]);
-        assert_eq!(graph.adjacent_nodes(3), vec![]);
-        assert_eq!(graph.adjacent_nodes(4), vec![]);
+        Some(from_to_n)
     }
 
-    #[test]
-    fn add_edge_undirected_graph_test() {
+    // Bellman-Ford法で1対nの最小距離を求める O(VE)
+    // fromから負閉路を経由して到達できる頂点はNegativeInfinity、到達できない頂点はUnreachableになる
+    pub fn bellman_ford(&self, from: usize) -> Vec<Distance<T>> {
+        let mut from_to_n: Vec<Option<T>> = vec![None; self.size];
+        from_to_n[from] = Some(T::zero());
+        for _ in 1..self.size {
+            let mut updated = false;
+            for &(from, to, cost) in &self.edges {
+                if let Some(dist) = from_to_n[from] {
+                    if from_to_n[to].is_none() || Some(dist + cost) < from_to_n[to] {
+                        from_to_n[to] = Some(dist + cost);
+                        updated = true;
+                    }
+                }
+            }
+            if !updated {
+                break;
+            }
+        }
+
+        // n-1周しても距離が更新される頂点と、そこから到達できる頂点は負閉路を経由できる
+        let mut negative = vec![false; self.size];
+        for _ in 0..self.size {
+            let mut updated = false;
+            for &(from, to, cost) in &self.edges {
+                if negative[to] {
+                    continue;
+                }
+                if let Some(dist) = from_to_n[from] {
+                    if negative[from]
+                        || from_to_n[to].is_none()
+                        || Some(dist + cost) < from_to_n[to]
+                    {
+                        negative[to] = true;
+                        updated = true;
+                    }
+                }
+            }
+            if !updated {
+                break;
+            }
+        }
+
+        (0..self.size)
+            .map(|v| match from_to_n[v] {
+                None => Distance::Unreachable,
+                Some(_) if negative[v] => Distance::NegativeInfinity,
+                Some(dist) => Distance::Finite(dist),
+            })
+            .collect()
+    }
+
+    // グラフ中の負閉路を1つ求める。負閉路がなければNone O(VE)
+    // 返り値[v0, v1, ..., vk-1]は、辺v0->v1, ..., vk-1->v0からなる閉路
+    pub fn negative_cycle(&self) -> Option<Vec<usize>> {
+        // すべての頂点を始点として、距離0から始める
+        let mut dists = vec![T::zero(); self.size];
+        let mut prev_nodes = vec![usize::MAX; self.size];
+        let mut last_updated = None;
+        for _ in 0..self.size {
+            last_updated = None;
+            for &(from, to, cost) in &self.edges {
+                if dists[from] + cost < dists[to] {
+                    dists[to] = dists[from] + cost;
+                    prev_nodes[to] = from;
+                    last_updated = Some(to);
+                }
+            }
+            last_updated?;
+        }
+
+        // n周目でも更新された頂点から辺をn回さかのぼると、負閉路上の頂点に着く
+        let mut v = last_updated?;
+        for _ in 0..self.size {
+            v = prev_nodes[v];
+        }
+
+        let mut cycle = vec![v];
+        let mut u = prev_nodes[v];
+        while u != v {
+            cycle.push(u);
+            u = prev_nodes[u];
+        }
+        cycle.reverse();
+
+        Some(cycle)
+    }
+
+    // min_dists_or_detect_negative_loopと同じ最短距離に加えて、最短路で各頂点の1つ前の頂点と辺を求める
+    // fromから到達できる負閉路を検出した場合はNone
+    pub fn shortest_path_tree_or_detect_negative_loop(
+        &self,
+        from: usize,
+    ) -> Option<ShortestPathTree<T>> {
+        let mut from_to_n = vec![T::max_value(); self.size];
+        let mut prev_nodes = vec![None; self.size];
+        let mut prev_edges = vec![None; self.size];
+        from_to_n[from] = T::zero();
+
+        // 負閉路がなければn-1周以内に更新がなくなる
+        let mut updated = true;
+        for _ in 0..self.size {
+            if !updated {
+                break;
+            }
+            updated = false;
+            for (edge_id, &(from, to, cost)) in self.edges.iter().enumerate() {
+                if from_to_n[from] != T::max_value() && from_to_n[from] + cost < from_to_n[to] {
+                    from_to_n[to] = from_to_n[from] + cost;
+                    prev_nodes[to] = Some(from);
+                    prev_edges[to] = Some(self.edge_id(edge_id));
+                    updated = true;
+                }
+            }
+        }
+
+        if updated {
+            None
+        } else {
+            Some(ShortestPathTree::new(
+                from, from_to_n, prev_nodes, prev_edges,
+            ))
+        }
+    }
+
+    // Warshall-Floyd法で全点対最短距離を求める O(V^3)
+    // 負閉路があっても求まり、負閉路を経由できる点対の距離はNegativeInfinityになる
+    pub fn warshall_floyd(&self) -> WarshallFloyd<T>
+    where
+        T: num::Saturating,
+    {
+        WarshallFloyd::from_edges(self.size, &self.edges)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::graph::distance::Distance;
+    use proptest::prelude::*;
+    use rand::prelude::*;
+
+    #[test]
+    fn add_edge_directed_graph_test() {
+        let mut graph = Graph::<usize>::new(5, true);
+        graph.add_edge(1, 0, 1);
+        graph.add_edge(1, 2, 1);
+        graph.add_edge(1, 3, 1);
+        graph.add_edge(1, 4, 1);
+
+        assert_eq!(graph.adjacent_nodes(1), vec![0, 2, 3, 4]);
+        assert_eq!(graph.adjacent_nodes(0), vec![]);
+        assert_eq!(graph.adjacent_nodes(2), vec![]);
+        assert_eq!(graph.adjacent_nodes(3), vec![]);
+        assert_eq!(graph.adjacent_nodes(4), vec![]);
+    }
+
+    #[test]
+    fn add_edge_undirected_graph_test() {
         let mut graph = Graph::<usize>::new(5, false);
         graph.add_edge(1, 0, 1);
         graph.add_edge(1, 2, 1);
@@ -447,6 +992,23 @@ mod test {
         assert_eq!(graph.adjacent_nodes(4), vec![1]);
     }
 
+    #[test]
+    fn csr_rebuild_test() {
+        let mut graph = Graph::<usize>::new(3, true);
+        graph.add_edge(0, 1, 5);
+        assert_eq!(
+            graph.min_dists(0),
+            vec![std::usize::MAX, 5, std::usize::MAX]
+        );
+        assert_eq!(graph.csr().adjacent(0), &[(1, 5)]);
+
+        // 隣接リストを作った後に辺を追加しても反映される
+        graph.add_edge(1, 2, 1);
+        graph.add_edge(0, 2, 3);
+        assert_eq!(graph.adjacent_nodes(0), vec![1, 2]);
+        assert_eq!(graph.min_dists(0), vec![std::usize::MAX, 5, 3]);
+    }
+
     #[test]
     fn min_dists_test() {
         let mut graph = Graph::<usize>::new(4, true);
@@ -563,6 +1125,287 @@ mod test {
         );
     }
 
+    #[test]
+    fn min_dists_radix_heap_test() {
+        let mut graph = Graph::<usize>::new(5, false);
+        graph.add_edge(0, 1, 90);
+        graph.add_edge(1, 2, 180);
+        graph.add_edge(0, 2, 150);
+        graph.add_edge(0, 3, 40);
+        graph.add_edge(3, 4, 9000);
+
+        assert_eq!(graph.min_dists_radix_heap(0), vec![80, 90, 150, 40, 9040]);
+        assert_eq!(
+            graph.min_dists_radix_heap(4),
+            vec![9040, 9130, 9190, 9000, 18000]
+        );
+
+        let mut graph = Graph::<u32>::new(3, true);
+        graph.add_edge(0, 1, u32::MAX - 1);
+        graph.add_edge(1, 2, 5);
+        assert_eq!(
+            graph.min_dists_radix_heap(0),
+            vec![u32::MAX, u32::MAX - 1, u32::MAX]
+        );
+    }
+
+    #[test]
+    fn min_dists_01_test() {
+        let mut graph = Graph::<usize>::new(6, true);
+        graph.add_edge(0, 1, 1);
+        graph.add_edge(0, 2, 0);
+        graph.add_edge(2, 3, 1);
+        graph.add_edge(1, 3, 0);
+        graph.add_edge(3, 4, 0);
+        graph.add_edge(4, 0, 1);
+
+        assert_eq!(graph.min_dists_01(0), vec![2, 1, 0, 1, 1, usize::MAX]);
+        assert_eq!(graph.min_dists_01(0), graph.min_dists(0));
+        assert_eq!(graph.min_dists_dial(0, 1), graph.min_dists(0));
+    }
+
+    #[test]
+    fn min_dists_dial_test() {
+        let mut graph = Graph::<u32>::new(5, false);
+        graph.add_edge(0, 1, 9);
+        graph.add_edge(1, 2, 8);
+        graph.add_edge(0, 2, 10);
+        graph.add_edge(0, 3, 4);
+        graph.add_edge(3, 4, 0);
+
+        assert_eq!(graph.min_dists_dial(0, 10), vec![8, 9, 10, 4, 4]);
+        assert_eq!(graph.min_dists_dial(2, 10), vec![10, 8, 16, 14, 14]);
+    }
+
+    proptest! {
+        #[test]
+        fn min_dists_01_random_test(graph_size :u8) {
+            let graph_size = graph_size as usize % 50 + 1;
+
+            let mut graph = Graph::<u64>::new(graph_size, true);
+            for _ in 0..graph_size * 3 {
+                let from: usize = random::<usize>() % graph_size;
+                let to: usize = random::<usize>() % graph_size;
+                graph.add_edge(from, to, random::<u64>() % 2);
+            }
+
+            for from in 0..graph_size {
+                prop_assert_eq!(graph.min_dists_01(from), graph.min_dists(from));
+            }
+        }
+
+        #[test]
+        fn min_dists_dial_random_test(graph_size :u8, max_cost :u8) {
+            let graph_size = graph_size as usize % 50 + 1;
+            let max_cost = max_cost as usize % 10;
+
+            let mut graph = Graph::<u64>::new(graph_size, true);
+            for _ in 0..graph_size * 3 {
+                let from: usize = random::<usize>() % graph_size;
+                let to: usize = random::<usize>() % graph_size;
+                graph.add_edge(from, to, random::<u64>() % (max_cost as u64 + 1));
+            }
+
+            for from in 0..graph_size {
+                prop_assert_eq!(graph.min_dists_dial(from, max_cost), graph.min_dists(from));
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn min_dists_radix_heap_random_test(graph_size :u8) {
+            let graph_size = graph_size as usize % 50 + 1;
+
+            let mut graph = Graph::<u64>::new(graph_size, true);
+            for _ in 0..graph_size * 3 {
+                let from: usize = random::<usize>() % graph_size;
+                let to: usize = random::<usize>() % graph_size;
+                graph.add_edge(from, to, random::<u64>() % 1000);
+            }
+
+            for from in 0..graph_size {
+                prop_assert_eq!(graph.min_dists_radix_heap(from), graph.min_dists(from));
+            }
+        }
+    }
+
+    #[test]
+    fn shortest_path_tree_test() {
+        let mut graph = Graph::<usize>::new(5, true);
+        graph.add_edge(0, 1, 90);
+        graph.add_edge(1, 2, 180);
+        graph.add_edge(0, 2, 150);
+        graph.add_edge(0, 3, 40);
+        graph.add_edge(3, 4, 9000);
+
+        let tree = graph.shortest_path_tree(0);
+        assert_eq!(tree.dists(), graph.min_dists(0).as_slice());
+        assert_eq!(tree.path_to(4), Some(vec![0, 3, 4]));
+        assert_eq!(tree.edge_path_to(4), Some(vec![3, 4]));
+        assert_eq!(tree.path_to(2), Some(vec![0, 2]));
+        assert_eq!(tree.edge_path_to(2), Some(vec![2]));
+        assert_eq!(tree.prev_node(1), Some(0));
+        assert_eq!(tree.path_to(0), Some(vec![0]));
+        assert_eq!(graph.shortest_path_tree(2).path_to(0), None);
+
+        // 無向グラフでも辺番号は追加した順番
+        let mut graph = Graph::<usize>::new(3, false);
+        graph.add_edge(1, 0, 5);
+        graph.add_edge(0, 2, 20);
+        graph.add_edge(2, 1, 7);
+        let tree = graph.shortest_path_tree(0);
+        assert_eq!(tree.edge_path_to(2), Some(vec![0, 2]));
+        assert_eq!(graph.edge(0), (1, 0, 5));
+        assert_eq!(graph.edge(2), (2, 1, 7));
+
+        // 始点に戻る閉路
+        let mut graph = Graph::<usize>::new(4, true);
+        graph.add_edge(0, 1, 100);
+        graph.add_edge(1, 2, 100);
+        graph.add_edge(2, 3, 150);
+        graph.add_edge(3, 0, 3);
+        let tree = graph.shortest_path_tree(0);
+        assert_eq!(tree.dists()[0], 353);
+        assert_eq!(tree.path_to(0), Some(vec![0, 1, 2, 3, 0]));
+    }
+
+    #[test]
+    fn shortest_path_tree_i64_test() {
+        let mut graph = Graph::<i64>::new(5, true);
+        graph.add_edge(0, 1, -50);
+        graph.add_edge(1, 0, 70);
+        graph.add_edge(1, 2, 180);
+        graph.add_edge(0, 2, 150);
+        graph.add_edge(0, 3, 40);
+        graph.add_edge(3, 4, 9000);
+
+        let tree = graph.shortest_path_tree_or_detect_negative_loop(0).unwrap();
+        assert_eq!(tree.dists(), &[0, -50, 130, 40, 9040]);
+        assert_eq!(tree.path_to(2), Some(vec![0, 1, 2]));
+        assert_eq!(tree.edge_path_to(2), Some(vec![0, 2]));
+        assert_eq!(tree.path_to(0), Some(vec![0]));
+
+        let mut graph = Graph::<i64>::new(3, true);
+        graph.add_edge(0, 1, -70);
+        graph.add_edge(1, 0, 69);
+        graph.add_edge(0, 2, 200);
+        assert!(graph
+            .shortest_path_tree_or_detect_negative_loop(0)
+            .is_none());
+        assert!(graph
+            .shortest_path_tree_or_detect_negative_loop(2)
+            .is_some());
+    }
+
+    proptest! {
+        #[test]
+        fn shortest_path_tree_random_test(graph_size :u8) {
+            let graph_size = graph_size as usize % 30 + 1;
+
+            let mut graph = Graph::<u64>::new(graph_size, random());
+            for _ in 0..graph_size * 2 {
+                let from: usize = random::<usize>() % graph_size;
+                let to: usize = random::<usize>() % graph_size;
+                graph.add_edge(from, to, random::<u64>() % 100);
+            }
+            for from in 0..graph_size {
+                let tree = graph.shortest_path_tree(from);
+                let dists = graph.min_dists(from);
+                prop_assert_eq!(tree.dists(), dists.as_slice());
+                for to in 0..graph_size {
+                    match tree.edge_path_to(to) {
+                        None => prop_assert_eq!(dists[to], u64::MAX),
+                        Some(edge_path) => {
+                            let path = tree.path_to(to).unwrap();
+                            prop_assert_eq!(path.len(), edge_path.len() + 1);
+                            let mut cost = 0;
+                            for (i, &edge_id) in edge_path.iter().enumerate() {
+                                // 無向グラフでは追加した向きと逆向きに通ることもある
+                                let (edge_from, edge_to, edge_cost) = graph.edge(edge_id);
+                                prop_assert!(
+                                    (edge_from, edge_to) == (path[i], path[i + 1])
+                                        || (!graph.directed && (edge_to, edge_from) == (path[i], path[i + 1]))
+                                );
+                                cost += edge_cost;
+                            }
+                            if !edge_path.is_empty() {
+                                prop_assert_eq!(cost, dists[to]);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn diameter_test() {
+        //    0
+        //   / \
+        //  1   2
+        //  |   |
+        //  3   4
+        //      |
+        //      5
+        let mut graph = Graph::<usize>::new(6, false);
+        graph.add_edge(0, 1, 4);
+        graph.add_edge(0, 2, 1);
+        graph.add_edge(1, 3, 2);
+        graph.add_edge(2, 4, 1);
+        graph.add_edge(4, 5, 3);
+
+        assert_eq!(graph.diameter(), (11, vec![3, 1, 0, 2, 4, 5]));
+        assert_eq!(graph.eccentricities(), vec![6, 9, 7, 11, 8, 11]);
+        assert_eq!(graph.center(), vec![0]);
+
+        // 辺の重みがすべて等しいと、中心は2つになりうる
+        let mut graph = Graph::<u32>::new(4, false);
+        graph.add_edge(0, 1, 1);
+        graph.add_edge(1, 2, 1);
+        graph.add_edge(2, 3, 1);
+        assert_eq!(graph.diameter(), (3, vec![3, 2, 1, 0]));
+        assert_eq!(graph.center(), vec![1, 2]);
+
+        let graph = Graph::<u32>::new(1, false);
+        assert_eq!(graph.diameter(), (0, vec![0]));
+        assert_eq!(graph.center(), vec![0]);
+    }
+
+    proptest! {
+        #[test]
+        fn diameter_random_test(graph_size :u8) {
+            let graph_size = graph_size as usize % 50 + 1;
+
+            let mut graph = Graph::<u64>::new(graph_size, false);
+            for v in 1..graph_size {
+                graph.add_edge(random::<usize>() % v, v, random::<u64>() % 100);
+            }
+
+            // 各頂点からDijkstraで求めた距離と比較する
+            let dists: Vec<Vec<u64>> = (0..graph_size)
+                .map(|v| {
+                    let mut dists = graph.min_dists(v);
+                    dists[v] = 0;
+                    dists
+                })
+                .collect();
+            let eccentricities: Vec<u64> = dists.iter().map(|d| *d.iter().max().unwrap()).collect();
+            prop_assert_eq!(graph.eccentricities(), eccentricities.clone());
+
+            let (length, path) = graph.diameter();
+            prop_assert_eq!(length, *eccentricities.iter().max().unwrap());
+            prop_assert_eq!(dists[path[0]][*path.last().unwrap()], length);
+            for i in 0..path.len() - 1 {
+                prop_assert!(graph.adjacent_nodes(path[i]).contains(&path[i + 1]));
+            }
+
+            let min = *eccentricities.iter().min().unwrap();
+            let center: Vec<usize> = (0..graph_size).filter(|&v| eccentricities[v] == min).collect();
+            prop_assert_eq!(graph.center(), center);
+        }
+    }
+
     #[test]
     fn min_dists_i64_test() {
         let mut graph = Graph::<i64>::new(5, true);
@@ -604,6 +1447,131 @@ mod test {
         assert_eq!(graph.min_dists_or_detect_negative_loop(0), None);
     }
 
+    #[test]
+    fn bellman_ford_test() {
+        let mut graph = Graph::<i64>::new(6, true);
+        graph.add_edge(0, 1, 3);
+        graph.add_edge(1, 2, -2);
+        graph.add_edge(2, 3, 4);
+        // 3 -> 4 -> 3 が負閉路になる
+        graph.add_edge(3, 4, -5);
+        graph.add_edge(4, 3, 1);
+        graph.add_edge(5, 0, 1);
+
+        assert_eq!(
+            graph.bellman_ford(0),
+            vec![
+                Distance::Finite(0),
+                Distance::Finite(3),
+                Distance::Finite(1),
+                Distance::NegativeInfinity,
+                Distance::NegativeInfinity,
+                Distance::Unreachable,
+            ]
+        );
+        assert_eq!(graph.min_dists_or_detect_negative_loop(0), None);
+        // 負閉路があっても、fromから到達できなければ関係ない
+        assert_eq!(
+            graph.bellman_ford(2)[..3],
+            [
+                Distance::Unreachable,
+                Distance::Unreachable,
+                Distance::Finite(0)
+            ]
+        );
+        // 閉路の始点はどこになってもよい
+        let cycle = graph.negative_cycle().unwrap();
+        assert!(cycle == vec![3, 4] || cycle == vec![4, 3]);
+
+        let mut graph = Graph::<i64>::new(3, true);
+        graph.add_edge(0, 1, 1);
+        graph.add_edge(1, 2, -1);
+        graph.add_edge(2, 0, 0);
+        assert_eq!(graph.negative_cycle(), None);
+        graph.add_edge(2, 2, -1);
+        assert_eq!(graph.negative_cycle(), Some(vec![2]));
+        assert_eq!(graph.min_dists_or_detect_negative_loop(0), None);
+    }
+
+    #[test]
+    fn bellman_ford_overflow_test() {
+        // 距離の総和はオーバーフローするが、各頂点の距離は範囲内に収まる
+        let mut graph = Graph::<i64>::new(3, true);
+        graph.add_edge(0, 1, i64::MAX / 2);
+        graph.add_edge(0, 2, i64::MAX / 2);
+        assert_eq!(
+            graph.min_dists_or_detect_negative_loop(0),
+            Some(vec![0, i64::MAX / 2, i64::MAX / 2])
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn bellman_ford_random_test(graph_size :u8) {
+            let graph_size = graph_size as usize % 8 + 1;
+
+            let mut graph = Graph::<i64>::new(graph_size, true);
+            for _ in 0..graph_size * 2 {
+                let from: usize = random::<usize>() % graph_size;
+                let to: usize = random::<usize>() % graph_size;
+                graph.add_edge(from, to, random::<i64>() % 20 - 3);
+            }
+            let edges: Vec<(usize, usize, i64)> = graph.edges.clone();
+            let warshall_floyd = WarshallFloyd::from_edges(graph_size, &edges);
+
+            for from in 0..graph_size {
+                let dists = graph.bellman_ford(from);
+                for to in 0..graph_size {
+                    prop_assert_eq!(dists[to], warshall_floyd.dist(from, to));
+                }
+            }
+
+            match graph.negative_cycle() {
+                Some(cycle) => {
+                    prop_assert!(warshall_floyd.has_negative_cycle());
+                    let mut total = 0;
+                    for i in 0..cycle.len() {
+                        let from = cycle[i];
+                        let to = cycle[(i + 1) % cycle.len()];
+                        let cost = graph
+                .edges
+                .iter()
+                .filter(|e| (e.0, e.1) == (from, to))
+                .map(|e| e.2)
+                .min()
+                .unwrap();
+                        total += cost;
+                    }
+                    prop_assert!(total < 0);
+                }
+                None => prop_assert!(!warshall_floyd.has_negative_cycle()),
+            }
+        }
+    }
+
+    #[test]
+    fn warshall_floyd_test() {
+        let mut graph = Graph::<i64>::new(4, true);
+        graph.add_edge(0, 1, 4);
+        graph.add_edge(1, 2, -3);
+        graph.add_edge(0, 2, 2);
+        graph.add_edge(2, 3, 1);
+
+        let warshall_floyd = graph.warshall_floyd();
+        assert_eq!(warshall_floyd.dist(0, 2), Distance::Finite(1));
+        assert_eq!(warshall_floyd.dist(0, 3), Distance::Finite(2));
+        assert_eq!(warshall_floyd.dist(3, 0), Distance::Unreachable);
+        assert_eq!(warshall_floyd.path(0, 3), Some(vec![0, 1, 2, 3]));
+
+        // 無向グラフの負辺は負閉路になる
+        let mut graph = Graph::<i64>::new(3, false);
+        graph.add_edge(0, 1, 5);
+        graph.add_edge(1, 2, -1);
+        let warshall_floyd = graph.warshall_floyd();
+        assert_eq!(warshall_floyd.dist(0, 1), Distance::NegativeInfinity);
+        assert!(warshall_floyd.has_negative_cycle());
+    }
+
     #[test]
     fn min_dists_undirected_test() {
         // 上のテストを無向グラフにしたもの
@@ -707,6 +1675,255 @@ mod test {
         assert_eq!(graph.euler_tour(0), vec![0, 1, 2, 1, 0, 3, 0]);
     }
 
+    #[test]
+    fn eulerian_trail_test() {
+        // 0 -> 1 -> 2 -> 0 -> 3, 1の自己ループ, 2 -> 0の多重辺と0 -> 2
+        let mut graph = Graph::new(4, true);
+        graph.add_edge(0, 1, 1);
+        graph.add_edge(1, 2, 1);
+        graph.add_edge(2, 0, 1);
+        graph.add_edge(0, 3, 1);
+        graph.add_edge(1, 1, 1);
+        graph.add_edge(2, 0, 1);
+        graph.add_edge(0, 2, 1);
+
+        assert_eq!(
+            graph.eulerian_trail(),
+            Some((vec![0, 1, 1, 2, 0, 2, 0, 3], vec![0, 4, 1, 2, 6, 5, 3]))
+        );
+        assert_eq!(graph.eulerian_circuit(), None);
+
+        graph.add_edge(3, 0, 1);
+        assert_eq!(graph.eulerian_circuit().unwrap().1.len(), 8);
+
+        // 辺が連結でない
+        let mut graph = Graph::new(4, true);
+        graph.add_edge(0, 1, 1);
+        graph.add_edge(1, 0, 1);
+        graph.add_edge(2, 3, 1);
+        graph.add_edge(3, 2, 1);
+        assert_eq!(graph.eulerian_trail(), None);
+
+        // 無向グラフでも辺番号は追加した順番。2番目の辺は追加と逆向きに通る
+        let mut graph = Graph::new(3, false);
+        graph.add_edge(0, 1, 1);
+        graph.add_edge(2, 1, 1);
+        graph.add_edge(2, 2, 1);
+        assert_eq!(
+            graph.eulerian_trail(),
+            Some((vec![0, 1, 2, 2], vec![0, 1, 2]))
+        );
+        assert_eq!(graph.eulerian_circuit(), None);
+        graph.add_edge(0, 2, 1);
+        assert_eq!(
+            graph.eulerian_circuit(),
+            Some((vec![0, 1, 2, 2, 0], vec![0, 1, 2, 3]))
+        );
+
+        let graph = Graph::<usize>::new(3, false);
+        assert_eq!(graph.eulerian_trail(), Some((vec![], vec![])));
+    }
+
+    proptest! {
+        #[test]
+        fn eulerian_trail_random_test(graph_size :u8, edge_size :u8, directed :bool) {
+            let graph_size = graph_size as usize % 5 + 1;
+            let edge_size = edge_size as usize % 8;
+
+            let mut graph = Graph::new(graph_size, directed);
+            let mut edges = vec![];
+            for _ in 0..edge_size {
+                let from = random::<usize>() % graph_size;
+                let to = random::<usize>() % graph_size;
+                graph.add_edge(from, to, 1);
+                edges.push((from, to));
+            }
+
+            // 全ての辺の使い方を試す
+            fn search(
+                v: usize,
+                edges: &[(usize, usize)],
+                directed: bool,
+                used: &mut Vec<bool>,
+                rest: usize,
+                circuit: Option<usize>,
+            ) -> bool {
+                if rest == 0 {
+                    return circuit.is_none() || circuit == Some(v);
+                }
+                for i in 0..edges.len() {
+                    let (from, to) = edges[i];
+                    let next = if from == v {
+                        to
+                    } else if !directed && to == v {
+                        from
+                    } else {
+                        continue;
+                    };
+                    if !used[i] {
+                        used[i] = true;
+                        let found = search(next, edges, directed, used, rest - 1, circuit);
+                        used[i] = false;
+                        if found {
+                            return true;
+                        }
+                    }
+                }
+                false
+            }
+            let mut used = vec![false; edge_size];
+            let trail_exists = edge_size == 0
+                || (0..graph_size).any(|v| search(v, &edges, directed, &mut used, edge_size, None));
+            let circuit_exists = edge_size == 0
+                || (0..graph_size).any(|v| search(v, &edges, directed, &mut used, edge_size, Some(v)));
+
+            prop_assert_eq!(graph.eulerian_trail().is_some(), trail_exists);
+            prop_assert_eq!(graph.eulerian_circuit().is_some(), circuit_exists);
+
+            for (vertices, edge_path) in graph.eulerian_trail().into_iter().chain(graph.eulerian_circuit()) {
+                prop_assert_eq!(edge_path.len(), edge_size);
+                prop_assert_eq!(vertices.len(), if edge_size == 0 { 0 } else { edge_size + 1 });
+                let mut ids = edge_path.clone();
+                ids.sort();
+                prop_assert_eq!(ids, (0..edge_size).collect::<Vec<_>>());
+                for (i, &id) in edge_path.iter().enumerate() {
+                    let (from, to) = edges[id];
+                    prop_assert!(
+                        (from, to) == (vertices[i], vertices[i + 1])
+                            || (!directed && (to, from) == (vertices[i], vertices[i + 1]))
+                    );
+                }
+            }
+            if let Some((vertices, _)) = graph.eulerian_circuit() {
+                prop_assert_eq!(vertices.first(), vertices.last());
+            }
+        }
+    }
+
+    #[test]
+    fn bipartite_coloring_test() {
+        // 0-1-2-3の閉路と、4-5
+        let mut graph = Graph::new(7, false);
+        graph.add_edge(0, 1, 1);
+        graph.add_edge(1, 2, 1);
+        graph.add_edge(2, 3, 1);
+        graph.add_edge(3, 0, 1);
+        graph.add_edge(5, 4, 1);
+        assert_eq!(
+            graph.bipartite_coloring(),
+            Ok(vec![
+                (vec![0, 2], vec![1, 3]),
+                (vec![4], vec![5]),
+                (vec![6], vec![])
+            ])
+        );
+
+        // 1-4と2-4を足すと、1-2-4が奇閉路になる
+        graph.add_edge(1, 4, 1);
+        graph.add_edge(2, 4, 1);
+        assert_eq!(graph.bipartite_coloring(), Err(vec![2, 1, 4]));
+
+        let mut graph = Graph::new(2, false);
+        graph.add_edge(1, 1, 1);
+        assert_eq!(graph.bipartite_coloring(), Err(vec![1]));
+    }
+
+    proptest! {
+        #[test]
+        fn bipartite_coloring_random_test(graph_size :u8, edge_size :u8) {
+            let graph_size = graph_size as usize % 10 + 1;
+            let edge_size = edge_size as usize % 12;
+
+            let mut graph = Graph::new(graph_size, false);
+            let mut edges = vec![];
+            for _ in 0..edge_size {
+                let from = random::<usize>() % graph_size;
+                let to = random::<usize>() % graph_size;
+                graph.add_edge(from, to, 1);
+                edges.push((from, to));
+            }
+            let has_edge = |u: usize, v: usize| edges.contains(&(u, v)) || edges.contains(&(v, u));
+
+            // 全ての塗り方を試す
+            let expected = (0..1usize << graph_size)
+                .any(|mask| edges.iter().all(|&(u, v)| (mask >> u) & 1 != (mask >> v) & 1));
+
+            match graph.bipartite_coloring() {
+                Ok(components) => {
+                    prop_assert!(expected);
+                    let mut colors = vec![None; graph_size];
+                    for (zeros, ones) in &components {
+                        for &v in zeros {
+                            prop_assert!(colors[v].is_none());
+                            colors[v] = Some(false);
+                        }
+                        for &v in ones {
+                            prop_assert!(colors[v].is_none());
+                            colors[v] = Some(true);
+                        }
+                    }
+                    for &(u, v) in &edges {
+                        prop_assert_ne!(colors[u], colors[v]);
+                    }
+                    prop_assert_eq!(components.len(), graph.scc().len());
+                }
+                Err(cycle) => {
+                    prop_assert!(!expected);
+                    prop_assert_eq!(cycle.len() % 2, 1);
+                    for i in 0..cycle.len() {
+                        prop_assert!(has_edge(cycle[i], cycle[(i + 1) % cycle.len()]));
+                    }
+                    let mut vertices = cycle.clone();
+                    vertices.sort();
+                    vertices.dedup();
+                    prop_assert_eq!(vertices.len(), cycle.len());
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn tree_cyclic_test() {
+        // 辺の数はn-1だが、0-1-2の閉路があり3が孤立している
+        let mut graph = Graph::new(4, false);
+        graph.add_edge(0, 1, 1);
+        graph.add_edge(1, 2, 1);
+        graph.add_edge(2, 0, 1);
+        graph.tree(0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn tree_extra_edge_test() {
+        let mut graph = Graph::new(3, false);
+        graph.add_edge(0, 1, 1);
+        graph.add_edge(1, 2, 1);
+        graph.add_edge(2, 0, 1);
+        graph.tree(0);
+    }
+
+    #[test]
+    fn lowlink_test() {
+        // 0-1-2の三角形と、1-3の橋。3-4は多重辺
+        let mut graph = Graph::new(5, false);
+        graph.add_edge(0, 1, 1);
+        graph.add_edge(1, 2, 1);
+        graph.add_edge(2, 0, 1);
+        graph.add_edge(1, 3, 1);
+        graph.add_edge(3, 4, 1);
+        graph.add_edge(4, 3, 1);
+
+        let lowlink = graph.lowlink();
+        assert_eq!(lowlink.bridges(), vec![3]);
+        assert_eq!(lowlink.articulation_points(), vec![1, 3]);
+        assert_eq!(
+            lowlink.two_edge_connected_components(),
+            vec![vec![0, 1, 2], vec![3, 4]]
+        );
+        assert_eq!(lowlink.biconnected_components().len(), 3);
+    }
+
     #[test]
     fn is_tree_test() {
         let mut graph = Graph::<usize>::new(5, false);
@@ -733,6 +1950,84 @@ mod test {
         assert_eq!(graph.is_tree(), false);
     }
 
+    #[test]
+    fn maximum_matching_test() {
+        // 0-1-2-0の三角形と、2-3-4-5のパス
+        let mut graph = Graph::new(7, false);
+        graph.add_edge(0, 1, 1);
+        graph.add_edge(1, 2, 1);
+        graph.add_edge(2, 0, 1);
+        graph.add_edge(2, 3, 1);
+        graph.add_edge(3, 4, 1);
+        graph.add_edge(4, 5, 1);
+        graph.add_edge(6, 6, 1);
+
+        let mates = graph.maximum_matching();
+        assert_eq!(mates.iter().flatten().count(), 6);
+        assert_eq!(mates[6], None);
+        for (v, &mate) in mates.iter().enumerate() {
+            if let Some(mate) = mate {
+                assert_eq!(mates[mate], Some(v));
+            }
+        }
+
+        // 三角形1-2-3の各頂点から1本ずつ辺が出ていて、完全マッチングがある
+        let mut graph = Graph::new(6, false);
+        graph.add_edge(1, 2, 1);
+        graph.add_edge(2, 3, 1);
+        graph.add_edge(3, 1, 1);
+        graph.add_edge(0, 1, 1);
+        graph.add_edge(2, 5, 1);
+        graph.add_edge(3, 4, 1);
+        assert_eq!(graph.maximum_matching().iter().flatten().count(), 6);
+    }
+
+    proptest! {
+        #[test]
+        fn maximum_matching_random_test(graph_size :u8) {
+            let graph_size = graph_size as usize % 10 + 1;
+
+            let mut graph = Graph::new(graph_size, false);
+            let mut edges = vec![];
+            for _ in 0..random::<usize>() % (graph_size * 2) {
+                let from = random::<usize>() % graph_size;
+                let to = random::<usize>() % graph_size;
+                graph.add_edge(from, to, 1);
+                edges.push((from, to));
+            }
+
+            let mates = graph.maximum_matching();
+            for (v, &mate) in mates.iter().enumerate() {
+                if let Some(mate) = mate {
+                    prop_assert_eq!(mates[mate], Some(v));
+                    prop_assert!(edges.contains(&(v, mate)) || edges.contains(&(mate, v)));
+                    prop_assert_ne!(mate, v);
+                }
+            }
+
+            // 辺の部分集合を全探索する
+            let mut expected = 0;
+            for mask in 0..1usize << edges.len() {
+                let mut used = vec![false; graph_size];
+                let mut ok = true;
+                for (i, &(from, to)) in edges.iter().enumerate() {
+                    if mask >> i & 1 == 1 {
+                        if from == to || used[from] || used[to] {
+                            ok = false;
+                            break;
+                        }
+                        used[from] = true;
+                        used[to] = true;
+                    }
+                }
+                if ok {
+                    expected = expected.max(mask.count_ones() as usize);
+                }
+            }
+            prop_assert_eq!(mates.iter().flatten().count(), expected * 2);
+        }
+    }
+
     proptest! {
         #[test]
         fn is_tree_random_test(graph_size :u8) {
@@ -761,5 +2056,17 @@ mod test {
         graph.add_edge(3, 4, 5);
 
         assert_eq!(graph.mst(), vec![4, 1, 6, 3]);
+
+        // 無向グラフでも辺番号は追加した順番
+        let mut graph = Graph::<usize>::new(4, false);
+        graph.add_edge(0, 1, 3);
+        graph.add_edge(1, 2, 1);
+        graph.add_edge(2, 0, 2);
+        graph.add_edge(3, 2, 4);
+        graph.add_edge(0, 3, 5);
+        let mst = graph.mst();
+        assert_eq!(mst, vec![1, 2, 3]);
+        let edges: Vec<(usize, usize, usize)> = mst.iter().map(|&id| graph.edge(id)).collect();
+        assert_eq!(edges, vec![(1, 2, 1), (2, 0, 2), (3, 2, 4)]);
     }
 }
@@ -0,0 +1,230 @@
+use cargo_snippet::snippet;
+
+// 二部グラフの最大マッチング(Hopcroft-Karp法) O(E√V)
+// 左側の頂点は0..left、右側の頂点は0..rightで、それぞれ別に番号を振る
+#[snippet("@BipartiteMatching")]
+pub struct BipartiteMatching {
+    left: usize,
+    right: usize,
+    adjacent: Vec<Vec<usize>>,
+    // match_left[l]: 左側の頂点lとマッチしている右側の頂点
+    match_left: Vec<Option<usize>>,
+    match_right: Vec<Option<usize>>,
+}
+
+#[snippet("@BipartiteMatching")]
+impl BipartiteMatching {
+    pub fn new(left: usize, right: usize) -> BipartiteMatching {
+        BipartiteMatching {
+            left,
+            right,
+            adjacent: vec![vec![]; left],
+            match_left: vec![None; left],
+            match_right: vec![None; right],
+        }
+    }
+
+    // 左側の頂点lと右側の頂点rを結ぶ辺を追加する
+    pub fn add_edge(&mut self, l: usize, r: usize) {
+        assert!(l < self.left && r < self.right);
+        self.adjacent[l].push(r);
+    }
+
+    // マッチしていない左側の頂点からの、交互路での距離
+    fn bfs(&self) -> Vec<usize> {
+        let mut dists = vec![usize::MAX; self.left];
+        let mut queue = std::collections::VecDeque::new();
+        for (l, matched) in self.match_left.iter().enumerate() {
+            if matched.is_none() {
+                dists[l] = 0;
+                queue.push_back(l);
+            }
+        }
+
+        while let Some(l) = queue.pop_front() {
+            for &r in &self.adjacent[l] {
+                if let Some(next) = self.match_right[r] {
+                    if dists[next] == usize::MAX {
+                        dists[next] = dists[l] + 1;
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+
+        dists
+    }
+
+    // lから始まる最短の増加路を探して、見つかればマッチングを更新する
+    fn dfs(&mut self, l: usize, dists: &[usize], edge_used: &mut [usize]) -> bool {
+        while edge_used[l] < self.adjacent[l].len() {
+            let r = self.adjacent[l][edge_used[l]];
+            edge_used[l] += 1;
+
+            let found = match self.match_right[r] {
+                None => true,
+                Some(next) => dists[next] == dists[l] + 1 && self.dfs(next, dists, edge_used),
+            };
+            if found {
+                self.match_left[l] = Some(r);
+                self.match_right[r] = Some(l);
+                return true;
+            }
+        }
+
+        false
+    }
+
+    // 最大マッチングの(左側の頂点, 右側の頂点)の組を、左側の頂点の昇順に返す
+    pub fn maximum_matching(&mut self) -> Vec<(usize, usize)> {
+        loop {
+            let dists = self.bfs();
+            let mut edge_used = vec![0; self.left];
+            let mut updated = false;
+            for l in 0..self.left {
+                if self.match_left[l].is_none() && self.dfs(l, &dists, &mut edge_used) {
+                    updated = true;
+                }
+            }
+            if !updated {
+                break;
+            }
+        }
+
+        (0..self.left)
+            .filter_map(|l| self.match_left[l].map(|r| (l, r)))
+            .collect()
+    }
+
+    // マッチしていない左側の頂点から交互路で到達できる頂点(左側, 右側)
+    fn alternating_reachable(&mut self) -> (Vec<bool>, Vec<bool>) {
+        self.maximum_matching();
+
+        let mut left_visited = vec![false; self.left];
+        let mut right_visited = vec![false; self.right];
+        let mut stack: Vec<usize> = (0..self.left)
+            .filter(|&l| self.match_left[l].is_none())
+            .collect();
+        stack.iter().for_each(|&l| left_visited[l] = true);
+        while let Some(l) = stack.pop() {
+            for &r in &self.adjacent[l] {
+                if right_visited[r] {
+                    continue;
+                }
+                right_visited[r] = true;
+                if let Some(next) = self.match_right[r] {
+                    if !left_visited[next] {
+                        left_visited[next] = true;
+                        stack.push(next);
+                    }
+                }
+            }
+        }
+
+        (left_visited, right_visited)
+    }
+
+    // 最小点被覆の(左側の頂点, 右側の頂点)。大きさは最大マッチングと等しい(Königの定理)
+    pub fn minimum_vertex_cover(&mut self) -> (Vec<usize>, Vec<usize>) {
+        let (left_visited, right_visited) = self.alternating_reachable();
+
+        (
+            (0..self.left).filter(|&l| !left_visited[l]).collect(),
+            (0..self.right).filter(|&r| right_visited[r]).collect(),
+        )
+    }
+
+    // 最大独立集合の(左側の頂点, 右側の頂点)。最小点被覆の補集合
+    pub fn maximum_independent_set(&mut self) -> (Vec<usize>, Vec<usize>) {
+        let (left_visited, right_visited) = self.alternating_reachable();
+
+        (
+            (0..self.left).filter(|&l| left_visited[l]).collect(),
+            (0..self.right).filter(|&r| !right_visited[r]).collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::graph::maximum_flow::MaximumFlow;
+    use proptest::prelude::*;
+    use rand::prelude::*;
+
+    #[test]
+    fn bipartite_matching_test() {
+        let mut matching = BipartiteMatching::new(3, 4);
+        matching.add_edge(0, 0);
+        matching.add_edge(0, 1);
+        matching.add_edge(1, 0);
+        matching.add_edge(2, 0);
+        matching.add_edge(2, 3);
+
+        assert_eq!(matching.maximum_matching(), vec![(0, 1), (1, 0), (2, 3)]);
+        assert_eq!(matching.minimum_vertex_cover(), (vec![0, 1, 2], vec![]));
+        assert_eq!(
+            matching.maximum_independent_set(),
+            (vec![], vec![0, 1, 2, 3])
+        );
+
+        let mut matching = BipartiteMatching::new(3, 2);
+        matching.add_edge(0, 0);
+        matching.add_edge(1, 0);
+        matching.add_edge(2, 0);
+        matching.add_edge(2, 1);
+
+        assert_eq!(matching.maximum_matching().len(), 2);
+        assert_eq!(matching.minimum_vertex_cover(), (vec![2], vec![0]));
+        assert_eq!(matching.maximum_independent_set(), (vec![0, 1], vec![1]));
+    }
+
+    proptest! {
+        #[test]
+        fn bipartite_matching_random_test(left :u8, right :u8) {
+            let left = left as usize % 10 + 1;
+            let right = right as usize % 10 + 1;
+            let mut matching = BipartiteMatching::new(left, right);
+            let mut maximum_flow = MaximumFlow::new(left + right + 2);
+            let mut edges = vec![];
+            for _ in 0..random::<usize>() % (left * right + 1) {
+                let l = random::<usize>() % left;
+                let r = random::<usize>() % right;
+                matching.add_edge(l, r);
+                maximum_flow.add_edge(l, left + r, 1);
+                edges.push((l, r));
+            }
+            for l in 0..left {
+                maximum_flow.add_edge(left + right, l, 1);
+            }
+            for r in 0..right {
+                maximum_flow.add_edge(left + r, left + right + 1, 1);
+            }
+
+            let pairs = matching.maximum_matching();
+            prop_assert_eq!(pairs.len(), maximum_flow.maximum_flow(left + right, left + right + 1));
+            for &pair in &pairs {
+                prop_assert!(edges.contains(&pair));
+            }
+            let mut left_used = vec![false; left];
+            let mut right_used = vec![false; right];
+            for &(l, r) in &pairs {
+                prop_assert!(!left_used[l] && !right_used[r]);
+                left_used[l] = true;
+                right_used[r] = true;
+            }
+
+            let (cover_left, cover_right) = matching.minimum_vertex_cover();
+            prop_assert_eq!(cover_left.len() + cover_right.len(), pairs.len());
+            for &(l, r) in &edges {
+                prop_assert!(cover_left.contains(&l) || cover_right.contains(&r));
+            }
+
+            let (set_left, set_right) = matching.maximum_independent_set();
+            prop_assert_eq!(set_left.len() + set_right.len(), left + right - pairs.len());
+            for &(l, r) in &edges {
+                prop_assert!(!(set_left.contains(&l) && set_right.contains(&r)));
+            }
+        }
+    }
+}
@@ -0,0 +1,238 @@
+use crate::graph::maximum_flow::MaximumFlow;
+use cargo_snippet::snippet;
+
+// 各辺の流量に下限と上限があるフロー。超頂点を追加してMaximumFlowに帰着する
+// 辺はadd_edgeで追加した順に0から番号が振られ、各メソッドは辺の番号順の流量を返す
+#[snippet("@BoundedFlow")]
+pub struct BoundedFlow<T> {
+    size: usize,
+    // (from, to, lower, upper)
+    edges: Vec<(usize, usize, T, T)>,
+}
+
+#[snippet("@BoundedFlow")]
+impl<T: num::PrimInt> BoundedFlow<T> {
+    pub fn new(n: usize) -> BoundedFlow<T> {
+        BoundedFlow {
+            size: n,
+            edges: vec![],
+        }
+    }
+
+    // from -> toに流量がlower以上upper以下の辺を追加し、辺の番号を返す
+    pub fn add_edge(&mut self, from: usize, to: usize, lower: T, upper: T) -> usize {
+        assert!(T::zero() <= lower && lower <= upper);
+        self.edges.push((from, to, lower, upper));
+        self.edges.len() - 1
+    }
+
+    // 下限の分を流したものとして、過不足を超頂点との辺で補うグラフを作る
+    // 返り値は(グラフ, 超頂点から流すべき流量)。超始点はsize、超終点はsize+1
+    fn reduce(&self) -> (MaximumFlow<T>, T) {
+        let source = self.size;
+        let sink = self.size + 1;
+        let mut graph = MaximumFlow::new(self.size + 2);
+        let mut excess = vec![(T::zero(), T::zero()); self.size];
+        for &(from, to, lower, upper) in &self.edges {
+            graph.add_edge(from, to, upper - lower);
+            // (入ってくる量, 出ていく量)
+            excess[to].0 = excess[to].0 + lower;
+            excess[from].1 = excess[from].1 + lower;
+        }
+
+        let mut demand = T::zero();
+        for (v, &(incoming, outgoing)) in excess.iter().enumerate() {
+            if incoming > outgoing {
+                graph.add_edge(source, v, incoming - outgoing);
+                demand = demand + (incoming - outgoing);
+            } else if incoming < outgoing {
+                graph.add_edge(v, sink, outgoing - incoming);
+            }
+        }
+
+        (graph, demand)
+    }
+
+    // 超頂点からの流量を満たせるか
+    fn is_feasible(&self, graph: &mut MaximumFlow<T>, demand: T) -> bool {
+        graph.maximum_flow(self.size, self.size + 1) == demand
+    }
+
+    fn flows(&self, graph: &MaximumFlow<T>) -> Vec<T> {
+        self.edges
+            .iter()
+            .enumerate()
+            .map(|(id, &(_, _, lower, _))| graph.get_edge(id).flow + lower)
+            .collect()
+    }
+
+    // 全ての頂点で流量保存則を満たす流し方(循環流)。存在しなければNone
+    pub fn circulation(&self) -> Option<Vec<T>> {
+        let (mut graph, demand) = self.reduce();
+        if !self.is_feasible(&mut graph, demand) {
+            return None;
+        }
+
+        Some(self.flows(&graph))
+    }
+
+    // 超頂点からの流量を満たした上で、fromとtoの間に容量無限の辺を両方向に加えたグラフ
+    // 返り値は(グラフ, to -> fromの辺で戻した流量, from -> toの辺で戻した流量)
+    fn feasible_st_flow(&self, from: usize, to: usize) -> Option<(MaximumFlow<T>, T, T)> {
+        let (mut graph, demand) = self.reduce();
+        // to -> fromの辺を加えると循環流の問題になる。流量が負になる場合のためにfrom -> toの辺も加える
+        let back_edge = graph.add_edge(to, from, T::max_value());
+        let forward_edge = graph.add_edge(from, to, T::max_value());
+        if !self.is_feasible(&mut graph, demand) {
+            return None;
+        }
+
+        let back_flow = graph.get_edge(back_edge).flow;
+        let forward_flow = graph.get_edge(forward_edge).flow;
+        graph.change_capacity(back_edge, T::zero(), T::zero());
+        graph.change_capacity(forward_edge, T::zero(), T::zero());
+
+        Some((graph, back_flow, forward_flow))
+    }
+
+    // fromからtoへの流量の最大値と、そのときの各辺の流量。条件を満たす流し方がなければNone
+    pub fn maximum_flow(&self, from: usize, to: usize) -> Option<(T, Vec<T>)> {
+        let (mut graph, back_flow, forward_flow) = self.feasible_st_flow(from, to)?;
+        let flow = back_flow + graph.maximum_flow(from, to) - forward_flow;
+
+        Some((flow, self.flows(&graph)))
+    }
+
+    // fromからtoへの流量の最小値と、そのときの各辺の流量。条件を満たす流し方がなければNone
+    // toからfromへ戻る辺があると流量が負になりうるので、その場合は符号付き整数を使う
+    pub fn minimum_flow(&self, from: usize, to: usize) -> Option<(T, Vec<T>)> {
+        let (mut graph, back_flow, forward_flow) = self.feasible_st_flow(from, to)?;
+        // toからfromへ押し戻せるだけ押し戻す
+        let flow = back_flow - (forward_flow + graph.maximum_flow(to, from));
+
+        Some((flow, self.flows(&graph)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+    use rand::prelude::*;
+
+    #[test]
+    fn bounded_flow_test() {
+        let mut graph = BoundedFlow::<i64>::new(4);
+        graph.add_edge(0, 1, 1, 3);
+        graph.add_edge(0, 2, 0, 2);
+        graph.add_edge(1, 3, 2, 4);
+        graph.add_edge(2, 3, 1, 1);
+        graph.add_edge(1, 2, 0, 1);
+
+        assert_eq!(graph.maximum_flow(0, 3).unwrap().0, 4);
+        assert_eq!(graph.minimum_flow(0, 3).unwrap().0, 3);
+        // 流量保存則を満たせない
+        assert_eq!(graph.circulation(), None);
+
+        let (flow, flows) = graph.minimum_flow(0, 3).unwrap();
+        assert_eq!(flows[0] + flows[1], flow);
+        assert_eq!(flows[2] + flows[3], flow);
+
+        graph.add_edge(3, 0, 4, 4);
+        let flows = graph.circulation().unwrap();
+        assert_eq!(flows[5], 4);
+        assert_eq!(flows[0] + flows[1], 4);
+        assert_eq!(flows[3], 1);
+
+        // 下限を満たせない
+        let mut graph = BoundedFlow::<u32>::new(3);
+        graph.add_edge(0, 1, 0, 2);
+        graph.add_edge(1, 2, 3, 5);
+        assert_eq!(graph.maximum_flow(0, 2), None);
+        assert_eq!(graph.minimum_flow(0, 2), None);
+    }
+
+    proptest! {
+        #[test]
+        fn bounded_flow_random_test(n :u8) {
+            let n = n as usize % 4 + 2;
+            let m = n + 1;
+            let mut graph = BoundedFlow::<i64>::new(n);
+            let mut edges = vec![];
+            for _ in 0..m {
+                let from = random::<usize>() % n;
+                let to = random::<usize>() % n;
+                let lower = random::<i64>().abs() % 3;
+                let upper = lower + random::<i64>().abs() % 3;
+                graph.add_edge(from, to, lower, upper);
+                edges.push((from, to, lower, upper));
+            }
+
+            // 各辺の流量を全探索する。条件を満たすなら各頂点の(入ってくる量 - 出ていく量)を返す
+            let balance = |flows: &[i64]| -> Option<Vec<i64>> {
+                let mut balance = vec![0; n];
+                for (&(from, to, lower, upper), &flow) in edges.iter().zip(flows) {
+                    if flow < lower || upper < flow {
+                        return None;
+                    }
+                    balance[from] -= flow;
+                    balance[to] += flow;
+                }
+                Some(balance)
+            };
+            // s-tフローならtに流れ込む量を返す
+            let check = |flows: &[i64]| -> Option<i64> {
+                let balance = balance(flows)?;
+                if (1..n - 1).all(|v| balance[v] == 0) {
+                    Some(balance[n - 1])
+                } else {
+                    None
+                }
+            };
+            let mut values = vec![];
+            let mut has_circulation = false;
+            let mut flows = vec![0; m];
+            loop {
+                if let Some(value) = check(&flows) {
+                    values.push(value);
+                }
+                if let Some(balance) = balance(&flows) {
+                    has_circulation |= balance.iter().all(|&b| b == 0);
+                }
+
+                // 次の組み合わせ
+                let mut i = 0;
+                while i < m && flows[i] == 4 {
+                    flows[i] = 0;
+                    i += 1;
+                }
+                if i == m {
+                    break;
+                }
+                flows[i] += 1;
+            }
+
+            match graph.maximum_flow(0, n - 1) {
+                Some((flow, flows)) => {
+                    prop_assert_eq!(Some(flow), values.iter().max().copied());
+                    prop_assert_eq!(check(&flows), Some(flow));
+                }
+                None => prop_assert!(values.is_empty()),
+            }
+            match graph.minimum_flow(0, n - 1) {
+                Some((flow, flows)) => {
+                    prop_assert_eq!(Some(flow), values.iter().min().copied());
+                    prop_assert_eq!(check(&flows), Some(flow));
+                }
+                None => prop_assert!(values.is_empty()),
+            }
+            match graph.circulation() {
+                Some(flows) => {
+                    prop_assert!(has_circulation);
+                    prop_assert!(balance(&flows).unwrap().iter().all(|&b| b == 0));
+                }
+                None => prop_assert!(!has_circulation),
+            }
+        }
+    }
+}
@@ -0,0 +1,246 @@
+use crate::graph::tree::Tree;
+use cargo_snippet::snippet;
+
+// 重心分解。木の重心を取り除いて残った部分木を再帰的に分解する O(n log n)
+// 重心を取り除いた各部分木の大きさは元の半分以下になるので、重心木の深さはO(log n)になる
+#[snippet("@CentroidDecomposition")]
+pub struct CentroidDecomposition {
+    adjacent: Vec<Vec<usize>>,
+    // 重心木での親
+    parents: Vec<Option<usize>>,
+    // 重心木での深さ。小さいほど先に取り除かれる
+    depths: Vec<usize>,
+    // 重心を取り除いた順
+    order: Vec<usize>,
+}
+
+#[snippet("@CentroidDecomposition")]
+impl CentroidDecomposition {
+    pub fn new<T: Copy + num::Num>(tree: &Tree<T>) -> CentroidDecomposition {
+        let n = tree.size();
+        let mut adjacent = vec![vec![]; n];
+        for v in 0..n {
+            if let Some(parent) = tree.parent(v) {
+                adjacent[v].push(parent);
+                adjacent[parent].push(v);
+            }
+        }
+
+        let mut parents = vec![None; n];
+        let mut depths = vec![0; n];
+        let mut order = Vec::with_capacity(n);
+        let mut removed = vec![false; n];
+        let mut sizes = vec![0; n];
+        // (成分の頂点, 成分の親の重心)
+        let mut queue = std::collections::VecDeque::new();
+        if n > 0 {
+            queue.push_back((tree.root(), None));
+        }
+        while let Some((start, parent_centroid)) = queue.pop_front() {
+            // startを根として成分内の部分木の大きさを求める
+            let mut component = vec![(start, usize::MAX)];
+            let mut index = 0;
+            while index < component.len() {
+                let (v, parent) = component[index];
+                index += 1;
+                for &to in &adjacent[v] {
+                    if to != parent && !removed[to] {
+                        component.push((to, v));
+                    }
+                }
+            }
+            for &(v, parent) in component.iter().rev() {
+                sizes[v] = 1 + adjacent[v]
+                    .iter()
+                    .filter(|&&to| to != parent && !removed[to])
+                    .map(|&to| sizes[to])
+                    .sum::<usize>();
+            }
+
+            // 部分木の大きさが成分の半分を超える子があれば、そちらへ移る
+            let total = component.len();
+            let mut centroid = start;
+            let mut parent = usize::MAX;
+            while let Some(&heavy) = adjacent[centroid]
+                .iter()
+                .find(|&&to| to != parent && !removed[to] && sizes[to] * 2 > total)
+            {
+                parent = centroid;
+                centroid = heavy;
+            }
+
+            removed[centroid] = true;
+            parents[centroid] = parent_centroid;
+            depths[centroid] = parent_centroid.map_or(0, |p: usize| depths[p] + 1);
+            order.push(centroid);
+            for &to in &adjacent[centroid] {
+                if !removed[to] {
+                    queue.push_back((to, Some(centroid)));
+                }
+            }
+        }
+
+        CentroidDecomposition {
+            adjacent,
+            parents,
+            depths,
+            order,
+        }
+    }
+
+    // 重心木の根(最初に取り除いた重心)
+    pub fn root(&self) -> usize {
+        self.order[0]
+    }
+
+    // 重心木での親
+    pub fn parent(&self, v: usize) -> Option<usize> {
+        self.parents[v]
+    }
+
+    // 重心木での深さ
+    pub fn depth(&self, v: usize) -> usize {
+        self.depths[v]
+    }
+
+    // 重心を取り除いた順。親は子より前に来る
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
+
+    // centroidを重心とする成分からcentroidを取り除いた各部分木を列挙する
+    // 各部分木は(頂点, centroidからの辺の数)の列で、centroidに隣接する頂点から始まるBFS順に並ぶ
+    pub fn components(&self, centroid: usize) -> impl Iterator<Item = Vec<(usize, usize)>> + '_ {
+        let depth = self.depths[centroid];
+        self.adjacent[centroid]
+            .iter()
+            .filter(move |&&to| self.depths[to] > depth)
+            .map(move |&to| {
+                let mut component = vec![(to, 1)];
+                let mut parents = vec![centroid];
+                let mut index = 0;
+                while index < component.len() {
+                    let (v, dist) = component[index];
+                    let parent = parents[index];
+                    index += 1;
+                    for &next in &self.adjacent[v] {
+                        if next != parent && self.depths[next] > depth {
+                            component.push((next, dist + 1));
+                            parents.push(v);
+                        }
+                    }
+                }
+                component
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use rand::prelude::*;
+
+    #[test]
+    fn centroid_decomposition_test() {
+        // 0-1-2-3-4-5-6のパス
+        let edges: Vec<(usize, usize, usize)> = (0..6).map(|v| (v, v + 1, 1)).collect();
+        let tree = Tree::new(7, &edges, 0);
+        let centroid_decomposition = CentroidDecomposition::new(&tree);
+
+        assert_eq!(centroid_decomposition.root(), 3);
+        assert_eq!(centroid_decomposition.parent(3), None);
+        assert_eq!(centroid_decomposition.parent(1), Some(3));
+        assert_eq!(centroid_decomposition.parent(5), Some(3));
+        assert_eq!(centroid_decomposition.parent(0), Some(1));
+        assert_eq!(centroid_decomposition.parent(6), Some(5));
+        assert_eq!(centroid_decomposition.depth(6), 2);
+        assert_eq!(centroid_decomposition.order()[0], 3);
+
+        let components: Vec<Vec<(usize, usize)>> = centroid_decomposition.components(3).collect();
+        assert_eq!(
+            components,
+            vec![vec![(2, 1), (1, 2), (0, 3)], vec![(4, 1), (5, 2), (6, 3)]]
+        );
+        let components: Vec<Vec<(usize, usize)>> = centroid_decomposition.components(5).collect();
+        assert_eq!(components, vec![vec![(4, 1)], vec![(6, 1)]]);
+        assert_eq!(centroid_decomposition.components(0).count(), 0);
+    }
+
+    // 距離がk以下の頂点の組の数
+    fn count_pairs(centroid_decomposition: &CentroidDecomposition, k: usize) -> usize {
+        // 距離の列から、和がk以下の組の数を数える
+        let count = |mut dists: Vec<usize>| {
+            dists.sort();
+            let mut result = 0;
+            let mut right = dists.len();
+            for left in 0..dists.len() {
+                while right > 0 && dists[left] + dists[right - 1] > k {
+                    right -= 1;
+                }
+                if right <= left {
+                    break;
+                }
+                result += right - left - 1;
+            }
+            result
+        };
+
+        // centroidを通るパスだけを数えるため、同じ部分木の中の組を除く
+        let mut result = 0;
+        let mut same_component = 0;
+        for &centroid in centroid_decomposition.order() {
+            let mut all_dists = vec![0];
+            for component in centroid_decomposition.components(centroid) {
+                let dists: Vec<usize> = component.iter().map(|&(_, dist)| dist).collect();
+                same_component += count(dists.clone());
+                all_dists.extend(dists);
+            }
+            result += count(all_dists);
+        }
+        result - same_component
+    }
+
+    proptest! {
+        #[test]
+        fn centroid_decomposition_random_test(n :u8, k :u8) {
+            let n = n as usize % 60 + 1;
+            let k = k as usize % 10;
+            let edges: Vec<(usize, usize, usize)> = (1..n)
+                .map(|v| (random::<usize>() % v, v, 1))
+                .collect();
+            let tree = Tree::new(n, &edges, random::<usize>() % n);
+            let centroid_decomposition = CentroidDecomposition::new(&tree);
+
+            let mut expected = 0;
+            for u in 0..n {
+                for v in u + 1..n {
+                    if tree.dist(u, v) <= k {
+                        expected += 1;
+                    }
+                }
+            }
+            prop_assert_eq!(count_pairs(&centroid_decomposition, k), expected);
+
+            // 重心木の部分木の大きさは親の半分以下
+            let mut sizes = vec![1; n];
+            for &v in centroid_decomposition.order().iter().rev() {
+                if let Some(parent) = centroid_decomposition.parent(v) {
+                    sizes[parent] += sizes[v];
+                    prop_assert_eq!(centroid_decomposition.depth(v), centroid_decomposition.depth(parent) + 1);
+                }
+            }
+            for v in 0..n {
+                if let Some(parent) = centroid_decomposition.parent(v) {
+                    prop_assert!(sizes[v] * 2 <= sizes[parent]);
+                }
+                for component in centroid_decomposition.components(v) {
+                    for &(w, dist) in &component {
+                        prop_assert_eq!(tree.dist(v, w), dist);
+                    }
+                }
+            }
+            prop_assert_eq!(sizes[centroid_decomposition.root()], n);
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use cargo_snippet::snippet;
+
+// Compressed sparse row形式の隣接リスト。GraphとSimpleGraphの各アルゴリズムで共有する
+// 頂点vから出る辺はelist[start[v]..start[v + 1]]に、元の辺の順番を保ったまま並ぶ
+#[snippet("@Csr")]
+#[snippet("@Graph")]
+#[snippet("@SimpleGraph")]
+#[snippet("@Tree")]
+#[snippet("@HeavyLightDecomposition")]
+#[snippet("@CentroidDecomposition")]
+#[snippet("@rerooting")]
+#[snippet("@scc_ids")]
+#[snippet("@TwoSat")]
+#[derive(Clone, Debug)]
+pub struct Csr<T> {
+    start: Vec<usize>,
+    elist: Vec<(usize, T)>,
+    // edge_ids[i]: elist[i]が元の辺の列で何番目の辺か
+    edge_ids: Vec<usize>,
+    // positions[id]: 元の辺の列でid番目の辺のelist内の位置
+    positions: Vec<usize>,
+}
+
+#[snippet("@Csr")]
+#[snippet("@Graph")]
+#[snippet("@SimpleGraph")]
+#[snippet("@Tree")]
+#[snippet("@HeavyLightDecomposition")]
+#[snippet("@CentroidDecomposition")]
+#[snippet("@rerooting")]
+#[snippet("@scc_ids")]
+#[snippet("@TwoSat")]
+impl<T: Copy> Csr<T> {
+    // edges: (from, to, cost)の列。辺の番号はedges内の添字になる O(n+m)
+    pub fn new(n: usize, edges: &[(usize, usize, T)]) -> Csr<T> {
+        let mut start = vec![0; n + 1];
+        edges.iter().for_each(|&(from, _, _)| {
+            start[from + 1] += 1;
+        });
+        for i in 1..=n {
+            start[i] += start[i - 1];
+        }
+
+        let mut counter = start.clone();
+        let mut elist = Vec::with_capacity(edges.len());
+        let mut edge_ids = vec![0; edges.len()];
+        edges
+            .iter()
+            .enumerate()
+            .for_each(|(edge_id, &(from, _, _))| {
+                edge_ids[counter[from]] = edge_id;
+                counter[from] += 1;
+            });
+        let mut positions = vec![0; edges.len()];
+        edge_ids
+            .iter()
+            .enumerate()
+            .for_each(|(position, &edge_id)| {
+                let (_, to, cost) = edges[edge_id];
+                elist.push((to, cost));
+                positions[edge_id] = position;
+            });
+
+        Csr {
+            start,
+            elist,
+            edge_ids,
+            positions,
+        }
+    }
+
+    // 頂点数
+    pub fn size(&self) -> usize {
+        self.start.len() - 1
+    }
+
+    // vから出る辺の(行き先, コスト)
+    pub fn adjacent(&self, v: usize) -> &[(usize, T)] {
+        &self.elist[self.start[v]..self.start[v + 1]]
+    }
+
+    // vから出る辺の番号。adjacent(v)と同じ順に並ぶ
+    pub fn edge_ids(&self, v: usize) -> &[usize] {
+        &self.edge_ids[self.start[v]..self.start[v + 1]]
+    }
+
+    // id番目の辺(from, to, cost) O(log n)
+    pub fn edge(&self, id: usize) -> (usize, usize, T) {
+        let position = self.positions[id];
+        let from = self.start.partition_point(|&start| start <= position) - 1;
+        let (to, cost) = self.elist[position];
+        (from, to, cost)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csr_test() {
+        let csr = Csr::new(4, &[(1, 2, 10), (0, 1, 5), (1, 3, 7), (3, 0, 1), (1, 0, 2)]);
+
+        assert_eq!(csr.size(), 4);
+        assert_eq!(csr.adjacent(0), &[(1, 5)]);
+        assert_eq!(csr.adjacent(1), &[(2, 10), (3, 7), (0, 2)]);
+        assert_eq!(csr.adjacent(2), &[]);
+        assert_eq!(csr.adjacent(3), &[(0, 1)]);
+        assert_eq!(csr.edge_ids(1), &[0, 2, 4]);
+        assert_eq!(csr.edge_ids(3), &[3]);
+        assert_eq!(csr.edge(0), (1, 2, 10));
+        assert_eq!(csr.edge(3), (3, 0, 1));
+        assert_eq!(csr.edge(4), (1, 0, 2));
+    }
+}
@@ -0,0 +1,27 @@
+use cargo_snippet::snippet;
+
+// 負辺を含むグラフでの2点間の距離
+#[snippet("@Distance")]
+#[snippet("@Graph")]
+#[snippet("@SimpleGraph")]
+#[snippet("@WarshallFloyd")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Distance<T> {
+    Finite(T),
+    Unreachable,
+    // 途中で負閉路を通れるので、いくらでも小さくできる
+    NegativeInfinity,
+}
+
+#[snippet("@Distance")]
+#[snippet("@Graph")]
+#[snippet("@SimpleGraph")]
+#[snippet("@WarshallFloyd")]
+impl<T> Distance<T> {
+    pub fn finite(self) -> Option<T> {
+        match self {
+            Distance::Finite(dist) => Some(dist),
+            _ => None,
+        }
+    }
+}
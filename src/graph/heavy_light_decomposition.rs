@@ -0,0 +1,366 @@
+use crate::graph::tree::Tree;
+use cargo_snippet::snippet;
+
+// HL分解。各頂点に0..nの番号を振り、木の上のパスをO(log n)個の番号の区間に分ける
+// 同じheavy pathの頂点は根に近い順に連続した番号になり、部分木も連続した区間になるので、SegTreeやLazySegTreeと組み合わせて使う
+// 辺の値を扱う場合は、辺の子側の頂点の番号に値を置く
+#[snippet("@HeavyLightDecomposition")]
+pub struct HeavyLightDecomposition {
+    parents: Vec<Option<usize>>,
+    depths: Vec<usize>,
+    subtree_sizes: Vec<usize>,
+    // heads[v]: vを含むheavy pathで最も根に近い頂点
+    heads: Vec<usize>,
+    // indices[v]: 頂点vの番号
+    indices: Vec<usize>,
+    // vertices[i]: 番号iの頂点
+    vertices: Vec<usize>,
+}
+
+#[snippet("@HeavyLightDecomposition")]
+impl HeavyLightDecomposition {
+    // O(n)
+    pub fn new<T: Copy + num::Num>(tree: &Tree<T>) -> HeavyLightDecomposition {
+        let n = tree.size();
+        let parents: Vec<Option<usize>> = (0..n).map(|v| tree.parent(v)).collect();
+        let subtree_sizes: Vec<usize> = (0..n).map(|v| tree.subtree_size(v)).collect();
+        let mut children = vec![vec![]; n];
+        for &v in tree.order() {
+            if let Some(parent) = parents[v] {
+                children[parent].push(v);
+            }
+        }
+
+        // heavy pathをたどる子を最後にスタックに積み、最初に訪れる
+        let mut heads = vec![tree.root(); n];
+        let mut indices = vec![0; n];
+        let mut vertices = Vec::with_capacity(n);
+        let mut stack = vec![tree.root()];
+        while let Some(v) = stack.pop() {
+            indices[v] = vertices.len();
+            vertices.push(v);
+
+            let heavy = children[v]
+                .iter()
+                .copied()
+                .max_by_key(|&child| subtree_sizes[child]);
+            for &child in &children[v] {
+                if Some(child) != heavy {
+                    heads[child] = child;
+                    stack.push(child);
+                }
+            }
+            if let Some(heavy) = heavy {
+                heads[heavy] = heads[v];
+                stack.push(heavy);
+            }
+        }
+
+        HeavyLightDecomposition {
+            parents,
+            depths: (0..n).map(|v| tree.depth(v)).collect(),
+            subtree_sizes,
+            heads,
+            indices,
+            vertices,
+        }
+    }
+
+    // 頂点vの番号
+    pub fn index(&self, v: usize) -> usize {
+        self.indices[v]
+    }
+
+    // 番号indexの頂点
+    pub fn vertex(&self, index: usize) -> usize {
+        self.vertices[index]
+    }
+
+    // 辺u-vの値を置く番号(子側の頂点の番号)
+    pub fn edge_index(&self, u: usize, v: usize) -> usize {
+        if self.parents[v] == Some(u) {
+            self.indices[v]
+        } else {
+            assert_eq!(self.parents[u], Some(v));
+            self.indices[u]
+        }
+    }
+
+    pub fn lca(&self, mut u: usize, mut v: usize) -> usize {
+        while self.heads[u] != self.heads[v] {
+            if self.depths[self.heads[u]] > self.depths[self.heads[v]] {
+                u = self.parents[self.heads[u]].unwrap();
+            } else {
+                v = self.parents[self.heads[v]].unwrap();
+            }
+        }
+
+        if self.depths[u] < self.depths[v] {
+            u
+        } else {
+            v
+        }
+    }
+
+    // uからvへのパスを、パスの順に並べた区間[l, r)の列に分ける
+    // reversedがtrueの区間は、パス上で番号r-1からlの向きにたどる
+    // edgeがtrueなら頂点ではなく辺の番号の区間を返す
+    pub fn path_segments(
+        &self,
+        mut u: usize,
+        mut v: usize,
+        edge: bool,
+    ) -> Vec<(usize, usize, bool)> {
+        // uから上る区間と、vから上る区間
+        let mut up = vec![];
+        let mut down = vec![];
+        while self.heads[u] != self.heads[v] {
+            if self.depths[self.heads[u]] >= self.depths[self.heads[v]] {
+                up.push((self.indices[self.heads[u]], self.indices[u] + 1, true));
+                u = self.parents[self.heads[u]].unwrap();
+            } else {
+                down.push((self.indices[self.heads[v]], self.indices[v] + 1, false));
+                v = self.parents[self.heads[v]].unwrap();
+            }
+        }
+
+        // 辺の場合はLCAの番号を含めない
+        let skip = if edge { 1 } else { 0 };
+        if self.indices[u] >= self.indices[v] {
+            if self.indices[v] + skip < self.indices[u] + 1 {
+                up.push((self.indices[v] + skip, self.indices[u] + 1, true));
+            }
+        } else if self.indices[u] + skip < self.indices[v] + 1 {
+            down.push((self.indices[u] + skip, self.indices[v] + 1, false));
+        }
+
+        up.extend(down.into_iter().rev());
+        up
+    }
+
+    // uとvの間のパスの区間[l, r)の列。順番と向きを問わない場合に使う
+    pub fn path_ranges(&self, u: usize, v: usize, edge: bool) -> Vec<(usize, usize)> {
+        self.path_segments(u, v, edge)
+            .into_iter()
+            .map(|(l, r, _)| (l, r))
+            .collect()
+    }
+
+    // vの部分木の区間[l, r)。edgeがtrueなら部分木に含まれる辺の区間
+    pub fn subtree_range(&self, v: usize, edge: bool) -> (usize, usize) {
+        let skip = if edge { 1 } else { 0 };
+        (
+            self.indices[v] + skip,
+            self.indices[v] + self.subtree_sizes[v],
+        )
+    }
+
+    // uからvへのパスの値をパスの順に畳み込む。パスが空ならNone
+    // query(l, r, reversed)は、reversedがfalseなら番号の昇順、trueなら番号の降順に[l, r)を畳み込んだ値を返す
+    // operationが可換ならreversedは無視してよい
+    pub fn fold_path<T>(
+        &self,
+        u: usize,
+        v: usize,
+        edge: bool,
+        mut query: impl FnMut(usize, usize, bool) -> T,
+        operation: impl Fn(T, T) -> T,
+    ) -> Option<T> {
+        self.path_segments(u, v, edge)
+            .into_iter()
+            .map(|(l, r, reversed)| query(l, r, reversed))
+            .fold(None, |acc, value| match acc {
+                Some(acc) => Some(operation(acc, value)),
+                None => Some(value),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_structure::lazy_seg_tree::LazySegTree;
+    use crate::data_structure::seg_tree::SegTree;
+    use proptest::prelude::*;
+    use rand::prelude::*;
+
+    #[test]
+    fn heavy_light_decomposition_test() {
+        //       0
+        //     /   \
+        //    1     2
+        //   / \     \
+        //  3   4     5
+        //            |
+        //            6
+        let edges = vec![
+            (0, 1, 1),
+            (0, 2, 1),
+            (1, 3, 1),
+            (1, 4, 1),
+            (2, 5, 1),
+            (5, 6, 1),
+        ];
+        let tree = Tree::new(7, &edges, 0);
+        let hld = HeavyLightDecomposition::new(&tree);
+
+        // 0-2-5-6がheavy path
+        assert_eq!(hld.index(0), 0);
+        assert_eq!(hld.index(2), 1);
+        assert_eq!(hld.index(5), 2);
+        assert_eq!(hld.index(6), 3);
+        assert_eq!(hld.index(1), 4);
+        for i in 0..7 {
+            assert_eq!(hld.index(hld.vertex(i)), i);
+        }
+        assert_eq!(hld.edge_index(5, 2), 2);
+        assert_eq!(hld.edge_index(1, 4), hld.index(4));
+        assert_eq!(hld.lca(3, 6), 0);
+        assert_eq!(hld.lca(3, 4), 1);
+        assert_eq!(hld.subtree_range(2, false), (1, 4));
+        assert_eq!(hld.subtree_range(2, true), (2, 4));
+
+        // 6 -> 5 -> 2 -> 0 -> 1 -> 3
+        let i3 = hld.index(3);
+        let i1 = hld.index(1);
+        let mut segments = hld.path_segments(6, 3, false);
+        assert_eq!(segments.remove(0), (0, 4, true));
+        let rest: Vec<usize> = segments
+            .iter()
+            .flat_map(|&(l, r, reversed)| {
+                assert!(!reversed);
+                l..r
+            })
+            .collect();
+        assert_eq!(rest, vec![i1, i3]);
+        assert_eq!(hld.path_segments(6, 2, true), vec![(2, 4, true)]);
+        assert_eq!(hld.path_segments(2, 6, true), vec![(2, 4, false)]);
+        assert_eq!(hld.path_segments(3, 3, true), vec![]);
+        assert_eq!(hld.path_segments(3, 3, false), vec![(i3, i3 + 1, true)]);
+    }
+
+    // 頂点の値を文字列として、パスの順に連結する
+    #[test]
+    fn heavy_light_decomposition_non_commutative_test() {
+        let edges = vec![
+            (0, 1, 1),
+            (0, 2, 1),
+            (1, 3, 1),
+            (1, 4, 1),
+            (2, 5, 1),
+            (5, 6, 1),
+        ];
+        let tree = Tree::new(7, &edges, 0);
+        let hld = HeavyLightDecomposition::new(&tree);
+
+        let names = ["a", "b", "c", "d", "e", "f", "g"];
+        let values: Vec<String> = (0..7).map(|i| names[hld.vertex(i)].to_string()).collect();
+        let mut forward = SegTree::new(values.clone(), |a, b| a + &b);
+        let mut backward = SegTree::new(values, |a, b| b + &a);
+
+        let fold =
+            |u: usize, v: usize, forward: &mut SegTree<String>, backward: &mut SegTree<String>| {
+                hld.fold_path(
+                    u,
+                    v,
+                    false,
+                    |l, r, reversed| {
+                        if reversed {
+                            backward.query(l, r)
+                        } else {
+                            forward.query(l, r)
+                        }
+                    },
+                    |a, b| a + &b,
+                )
+            };
+        assert_eq!(
+            fold(6, 3, &mut forward, &mut backward),
+            Some("gfcabd".to_string())
+        );
+        assert_eq!(
+            fold(3, 6, &mut forward, &mut backward),
+            Some("dbacfg".to_string())
+        );
+        assert_eq!(
+            fold(4, 3, &mut forward, &mut backward),
+            Some("ebd".to_string())
+        );
+        assert_eq!(
+            fold(5, 5, &mut forward, &mut backward),
+            Some("f".to_string())
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn heavy_light_decomposition_random_test(n :u8) {
+            let n = n as usize % 40 + 1;
+            let edges: Vec<(usize, usize, usize)> = (1..n)
+                .map(|v| (random::<usize>() % v, v, 1))
+                .collect();
+            let tree = Tree::new(n, &edges, random::<usize>() % n);
+            let hld = HeavyLightDecomposition::new(&tree);
+
+            // 1次関数x -> ax + bの合成(非可換)をパスの順に行う
+            let modulo = 1_000_000_007u64;
+            let compose = move |f: (u64, u64), g: (u64, u64)| (f.0 * g.0 % modulo, (f.1 * g.0 + g.1) % modulo);
+            let values: Vec<(u64, u64)> = (0..n).map(|_| (random::<u64>() % 100, random::<u64>() % 100)).collect();
+            let ordered: Vec<(u64, u64)> = (0..n).map(|i| values[hld.vertex(i)]).collect();
+            let mut forward = SegTree::new(ordered.clone(), |f, g| (f.0 * g.0 % 1_000_000_007, (f.1 * g.0 + g.1) % 1_000_000_007));
+            let mut sum = SegTree::new(ordered.iter().map(|f| f.0).collect(), |a, b| a + b);
+            let mut backward = SegTree::new(ordered, |g, f| (f.0 * g.0 % 1_000_000_007, (f.1 * g.0 + g.1) % 1_000_000_007));
+
+            // 辺の重みとして、パスに1を足してパスの最大値を求める
+            let mut edge_weights = vec![0usize; n];
+            let mut lazy = LazySegTree::<usize, usize>::new(
+                vec![0; n],
+                |a, b| std::cmp::max(a, b),
+                |target, effect| target + effect,
+                |effect1, effect2| effect1 + effect2,
+            );
+
+            for _ in 0..20 {
+                let u = random::<usize>() % n;
+                let v = random::<usize>() % n;
+                let path: Vec<usize> = (0..=tree.dist(u, v)).map(|k| tree.jump(u, v, k).unwrap()).collect();
+
+                let expected = path.iter().map(|&w| values[w]).reduce(compose);
+                let actual = hld.fold_path(
+                    u,
+                    v,
+                    false,
+                    |l, r, reversed| if reversed { backward.query(l, r) } else { forward.query(l, r) },
+                    compose,
+                );
+                prop_assert_eq!(actual, expected);
+
+                // 可換な演算なら1つのSegTreeで向きを無視して畳み込める
+                let expected = path.iter().map(|&w| values[w].0).sum::<u64>();
+                let actual = hld.fold_path(u, v, false, |l, r, _| sum.query(l, r), |a, b| a + b);
+                prop_assert_eq!(actual, Some(expected));
+
+                let covered: usize = hld.path_ranges(u, v, true).iter().map(|&(l, r)| r - l).sum();
+                prop_assert_eq!(covered, path.len() - 1);
+                for &(l, r) in &hld.path_ranges(u, v, true) {
+                    lazy.apply_range(l, r, 1);
+                }
+                for i in 0..path.len() - 1 {
+                    edge_weights[hld.edge_index(path[i], path[i + 1])] += 1;
+                }
+                let w = random::<usize>() % n;
+                let (l, r) = hld.subtree_range(w, true);
+                if l < r {
+                    prop_assert_eq!(lazy.query(l, r), *edge_weights[l..r].iter().max().unwrap());
+                }
+
+                let (l, r) = hld.subtree_range(w, false);
+                let mut subtree: Vec<usize> = (l..r).map(|i| hld.vertex(i)).collect();
+                subtree.sort();
+                let expected: Vec<usize> = (0..n).filter(|&x| tree.lca(x, w) == w).collect();
+                prop_assert_eq!(subtree, expected);
+                prop_assert_eq!(hld.lca(u, v), tree.lca(u, v));
+            }
+        }
+    }
+}
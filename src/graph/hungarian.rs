@@ -0,0 +1,201 @@
+use cargo_snippet::snippet;
+
+// hungarianの結果
+#[snippet("@hungarian")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Assignment<T> {
+    // 割り当てたコストの合計の最小値
+    pub cost: T,
+    // row_to_column[i]: 行iを割り当てた列。行の方が多ければ割り当てられない行がある
+    pub row_to_column: Vec<Option<usize>>,
+    pub column_to_row: Vec<Option<usize>>,
+    // 双対変数。全てのi,jでrow_potentials[i] + column_potentials[j] <= costs[i][j]を満たし、
+    // 割り当てた(i, j)では等号が成り立つ
+    pub row_potentials: Vec<T>,
+    pub column_potentials: Vec<T>,
+}
+
+// 長方形のコスト行列costsについて、行と列をできるだけ多く1対1に割り当てるときのコストの最小値を求める(ハンガリアン法)
+// O(n^2 m) (n = min(行数, 列数), m = max(行数, 列数))
+#[snippet("@hungarian")]
+pub fn hungarian<T: Copy + num::Signed + num::Bounded + std::cmp::Ord>(
+    costs: &[Vec<T>],
+) -> Assignment<T> {
+    let rows = costs.len();
+    let columns = if rows == 0 { 0 } else { costs[0].len() };
+    // 列がなければ何も割り当てられない。転置すると行数が失われるので先に処理する
+    if columns == 0 {
+        return Assignment {
+            cost: T::zero(),
+            row_to_column: vec![None; rows],
+            column_to_row: vec![],
+            row_potentials: vec![T::zero(); rows],
+            column_potentials: vec![],
+        };
+    }
+    // 行の方が多ければ転置して解く
+    if rows > columns {
+        let transposed: Vec<Vec<T>> = (0..columns)
+            .map(|j| (0..rows).map(|i| costs[i][j]).collect())
+            .collect();
+        let assignment = hungarian(&transposed);
+        return Assignment {
+            cost: assignment.cost,
+            row_to_column: assignment.column_to_row,
+            column_to_row: assignment.row_to_column,
+            row_potentials: assignment.column_potentials,
+            column_potentials: assignment.row_potentials,
+        };
+    }
+
+    // 0番目は番兵として使い、行と列は1-indexedで扱う
+    let mut row_potentials = vec![T::zero(); rows + 1];
+    let mut column_potentials = vec![T::zero(); columns + 1];
+    // matched_rows[j]: 列jに割り当てた行
+    let mut matched_rows = vec![0; columns + 1];
+    for i in 1..=rows {
+        matched_rows[0] = i;
+        let mut column = 0;
+        // min_slacks[j]: 交互木に含まれる行から列jへの辺の、被約コストの最小値
+        let mut min_slacks = vec![T::max_value(); columns + 1];
+        let mut prev_columns = vec![0; columns + 1];
+        let mut used = vec![false; columns + 1];
+        // 割り当てられていない列に着くまで交互木を広げる
+        loop {
+            used[column] = true;
+            let row = matched_rows[column];
+            let mut delta = T::max_value();
+            let mut next_column = 0;
+            for j in 1..=columns {
+                if used[j] {
+                    continue;
+                }
+                let slack = costs[row - 1][j - 1] - row_potentials[row] - column_potentials[j];
+                if slack < min_slacks[j] {
+                    min_slacks[j] = slack;
+                    prev_columns[j] = column;
+                }
+                if min_slacks[j] < delta {
+                    delta = min_slacks[j];
+                    next_column = j;
+                }
+            }
+
+            for j in 0..=columns {
+                if used[j] {
+                    row_potentials[matched_rows[j]] = row_potentials[matched_rows[j]] + delta;
+                    column_potentials[j] = column_potentials[j] - delta;
+                } else {
+                    min_slacks[j] = min_slacks[j] - delta;
+                }
+            }
+
+            column = next_column;
+            if matched_rows[column] == 0 {
+                break;
+            }
+        }
+
+        // 増加路に沿って割り当てを入れ替える
+        while column != 0 {
+            let prev_column = prev_columns[column];
+            matched_rows[column] = matched_rows[prev_column];
+            column = prev_column;
+        }
+    }
+
+    let mut row_to_column = vec![None; rows];
+    let mut column_to_row = vec![None; columns];
+    let mut cost = T::zero();
+    for j in 1..=columns {
+        if matched_rows[j] != 0 {
+            row_to_column[matched_rows[j] - 1] = Some(j - 1);
+            column_to_row[j - 1] = Some(matched_rows[j] - 1);
+            cost = cost + costs[matched_rows[j] - 1][j - 1];
+        }
+    }
+
+    Assignment {
+        cost,
+        row_to_column,
+        column_to_row,
+        row_potentials: row_potentials[1..].to_vec(),
+        column_potentials: column_potentials[1..].to_vec(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::Itertools;
+    use proptest::prelude::*;
+    use rand::prelude::*;
+
+    #[test]
+    fn hungarian_test() {
+        let costs = vec![vec![4i64, 1, 3], vec![2, 0, 5], vec![3, 2, 2]];
+        let assignment = hungarian(&costs);
+        assert_eq!(assignment.cost, 5);
+        assert_eq!(assignment.row_to_column, vec![Some(1), Some(0), Some(2)]);
+        assert_eq!(assignment.column_to_row, vec![Some(1), Some(0), Some(2)]);
+
+        // 行の方が多い
+        let costs = vec![vec![-3i32, 5], vec![1, -10], vec![-4, 0]];
+        let assignment = hungarian(&costs);
+        assert_eq!(assignment.cost, -14);
+        assert_eq!(assignment.row_to_column, vec![None, Some(1), Some(0)]);
+        assert_eq!(assignment.column_to_row, vec![Some(2), Some(1)]);
+
+        let assignment = hungarian::<i32>(&[]);
+        assert_eq!(assignment.cost, 0);
+        assert!(assignment.row_to_column.is_empty());
+
+        let assignment = hungarian::<i32>(&[vec![], vec![]]);
+        assert_eq!(assignment.cost, 0);
+        assert_eq!(assignment.row_to_column, vec![None, None]);
+        assert!(assignment.column_to_row.is_empty());
+        assert_eq!(assignment.row_potentials, vec![0, 0]);
+    }
+
+    proptest! {
+        #[test]
+        fn hungarian_random_test(rows :u8, columns :u8) {
+            let rows = rows as usize % 5 + 1;
+            let columns = columns as usize % 5 + 1;
+            let costs: Vec<Vec<i64>> = (0..rows)
+                .map(|_| (0..columns).map(|_| random::<i64>() % 100).collect())
+                .collect();
+            let assignment = hungarian(&costs);
+
+            // 全ての割り当て方を試す
+            let expected = if rows <= columns {
+                (0..columns)
+                    .permutations(rows)
+                    .map(|p| (0..rows).map(|i| costs[i][p[i]]).sum::<i64>())
+                    .min()
+                    .unwrap()
+            } else {
+                (0..rows)
+                    .permutations(columns)
+                    .map(|p| (0..columns).map(|j| costs[p[j]][j]).sum::<i64>())
+                    .min()
+                    .unwrap()
+            };
+            prop_assert_eq!(assignment.cost, expected);
+
+            let mut total = 0;
+            for i in 0..rows {
+                for j in 0..columns {
+                    prop_assert!(assignment.row_potentials[i] + assignment.column_potentials[j] <= costs[i][j]);
+                }
+                if let Some(j) = assignment.row_to_column[i] {
+                    prop_assert_eq!(assignment.column_to_row[j], Some(i));
+                    prop_assert_eq!(assignment.row_potentials[i] + assignment.column_potentials[j], costs[i][j]);
+                    total += costs[i][j];
+                }
+            }
+            prop_assert_eq!(total, expected);
+            prop_assert_eq!(assignment.row_to_column.iter().flatten().count(), rows.min(columns));
+        }
+    }
+}
@@ -0,0 +1,444 @@
+use cargo_snippet::snippet;
+
+// 無向グラフのlowlink。橋・関節点・二重辺連結成分・二重頂点連結成分を求める O(n+m)
+// 辺の番号はnewに渡したedgesの添字。多重辺は区別し、自己ループはどの結果にも影響しない
+#[snippet("@LowLink")]
+#[snippet("@Graph")]
+pub struct LowLink {
+    edges: Vec<(usize, usize)>,
+    // DFSでの訪問順
+    ords: Vec<usize>,
+    // DFS木の辺を下り、後退辺を高々1本使って着ける頂点のordの最小値
+    lows: Vec<usize>,
+    // DFS木で親から入る辺
+    parent_edges: Vec<Option<usize>>,
+    // DFSの訪問順に並べた頂点
+    order: Vec<usize>,
+    // 二重頂点連結成分(ブロック)ごとの頂点と辺
+    block_vertices: Vec<Vec<usize>>,
+    block_edges: Vec<Vec<usize>>,
+}
+
+#[snippet("@LowLink")]
+#[snippet("@Graph")]
+impl LowLink {
+    // edges: 無向辺(u, v)の列 O(n+m)
+    pub fn new(n: usize, edges: &[(usize, usize)]) -> LowLink {
+        let mut adjacent = vec![vec![]; n];
+        for (id, &(u, v)) in edges.iter().enumerate() {
+            if u != v {
+                adjacent[u].push((v, id));
+                adjacent[v].push((u, id));
+            }
+        }
+
+        let mut ords = vec![usize::MAX; n];
+        let mut lows = vec![usize::MAX; n];
+        let mut parent_edges = vec![None; n];
+        let mut order = Vec::with_capacity(n);
+        let mut block_edges = vec![];
+        // まだブロックに割り当てていない辺
+        let mut edge_stack = vec![];
+        for root in 0..n {
+            if ords[root] != usize::MAX {
+                continue;
+            }
+            ords[root] = order.len();
+            lows[root] = order.len();
+            order.push(root);
+            if adjacent[root].is_empty() {
+                block_edges.push(vec![]);
+            }
+
+            // (頂点, 次に見る隣接辺の添字)
+            let mut stack = vec![(root, 0)];
+            while let Some(&mut (v, ref mut index)) = stack.last_mut() {
+                if let Some(&(to, id)) = adjacent[v].get(*index) {
+                    *index += 1;
+                    if parent_edges[v] == Some(id) {
+                        continue;
+                    }
+                    if ords[to] == usize::MAX {
+                        ords[to] = order.len();
+                        lows[to] = order.len();
+                        order.push(to);
+                        parent_edges[to] = Some(id);
+                        edge_stack.push(id);
+                        stack.push((to, 0));
+                    } else if ords[to] < ords[v] {
+                        // 後退辺
+                        lows[v] = std::cmp::min(lows[v], ords[to]);
+                        edge_stack.push(id);
+                    }
+                    continue;
+                }
+
+                stack.pop();
+                if let Some(&(parent, _)) = stack.last() {
+                    lows[parent] = std::cmp::min(lows[parent], lows[v]);
+                    // vの部分木からparentより上に戻れないので、parentで切れる
+                    if lows[v] >= ords[parent] {
+                        let mut block = vec![];
+                        while let Some(id) = edge_stack.pop() {
+                            block.push(id);
+                            if parent_edges[v] == Some(id) {
+                                break;
+                            }
+                        }
+                        block_edges.push(block);
+                    }
+                }
+            }
+        }
+
+        // 各ブロックの辺の端点を重複なく集める。辺のないブロックは孤立点
+        let mut isolated = (0..n).filter(|&v| adjacent[v].is_empty());
+        let mut last_blocks = vec![usize::MAX; n];
+        let block_vertices = block_edges
+            .iter()
+            .enumerate()
+            .map(|(i, block)| {
+                if block.is_empty() {
+                    return vec![isolated.next().unwrap()];
+                }
+                let mut vertices = vec![];
+                for &id in block {
+                    let (u, v) = edges[id];
+                    for w in [u, v] {
+                        if last_blocks[w] != i {
+                            last_blocks[w] = i;
+                            vertices.push(w);
+                        }
+                    }
+                }
+                vertices
+            })
+            .collect();
+
+        LowLink {
+            edges: edges.to_vec(),
+            ords,
+            lows,
+            parent_edges,
+            order,
+            block_vertices,
+            block_edges,
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.ords.len()
+    }
+
+    // 辺idが橋(取り除くと連結成分が増える辺)かどうか
+    pub fn is_bridge(&self, id: usize) -> bool {
+        let (u, v) = self.edges[id];
+        // DFS木の辺でなければ、後退辺か自己ループなので橋ではない
+        let child = if self.parent_edges[u] == Some(id) {
+            u
+        } else if self.parent_edges[v] == Some(id) {
+            v
+        } else {
+            return false;
+        };
+        self.lows[child] == self.ords[child]
+    }
+
+    // 橋の辺番号を昇順に列挙する
+    pub fn bridges(&self) -> Vec<usize> {
+        (0..self.edges.len())
+            .filter(|&id| self.is_bridge(id))
+            .collect()
+    }
+
+    // vが関節点(取り除くと連結成分が増える頂点)かどうか O(n)
+    pub fn is_articulation_point(&self, v: usize) -> bool {
+        // DFS木の子で、vより上に戻れないものの数
+        let children = (0..self.size())
+            .filter(|&w| self.parent(w) == Some(v) && self.lows[w] >= self.ords[v])
+            .count();
+        if self.parent_edges[v].is_none() {
+            children >= 2
+        } else {
+            children >= 1
+        }
+    }
+
+    // 関節点を昇順に列挙する O(n)
+    pub fn articulation_points(&self) -> Vec<usize> {
+        let mut counts = vec![0; self.size()];
+        for &v in &self.order {
+            if let Some(parent) = self.parent(v) {
+                if self.lows[v] >= self.ords[parent] {
+                    counts[parent] += 1;
+                }
+            }
+        }
+
+        (0..self.size())
+            .filter(|&v| {
+                if self.parent_edges[v].is_none() {
+                    counts[v] >= 2
+                } else {
+                    counts[v] >= 1
+                }
+            })
+            .collect()
+    }
+
+    // 二重辺連結成分(橋を取り除いた連結成分)の(成分数, 各頂点の成分番号)
+    // 成分番号は、成分内で最初にDFSで訪れた頂点の訪問順
+    pub fn two_edge_connected_component_ids(&self) -> (usize, Vec<usize>) {
+        let mut count = 0;
+        let mut ids = vec![0; self.size()];
+        for &v in &self.order {
+            match self.parent(v) {
+                Some(parent) if self.lows[v] != self.ords[v] => ids[v] = ids[parent],
+                _ => {
+                    ids[v] = count;
+                    count += 1;
+                }
+            }
+        }
+        (count, ids)
+    }
+
+    pub fn two_edge_connected_components(&self) -> Vec<Vec<usize>> {
+        let (count, ids) = self.two_edge_connected_component_ids();
+        let mut groups = vec![vec![]; count];
+        for (v, &id) in ids.iter().enumerate() {
+            groups[id].push(v);
+        }
+        groups
+    }
+
+    // 二重辺連結成分を頂点とし、橋を辺とする森の辺(成分番号, 成分番号, 橋の辺番号)の列
+    // 成分番号はtwo_edge_connected_component_idsと同じ
+    pub fn bridge_tree(&self) -> Vec<(usize, usize, usize)> {
+        let (_, ids) = self.two_edge_connected_component_ids();
+        self.bridges()
+            .into_iter()
+            .map(|id| {
+                let (u, v) = self.edges[id];
+                (ids[u], ids[v], id)
+            })
+            .collect()
+    }
+
+    // 二重頂点連結成分(ブロック)ごとの頂点の列。関節点は複数のブロックに含まれる
+    // 孤立点(自己ループのみの頂点を含む)はそれだけで1つのブロックになる
+    pub fn biconnected_components(&self) -> &[Vec<usize>] {
+        &self.block_vertices
+    }
+
+    // biconnected_componentsと同じ順の、各ブロックに含まれる辺番号の列。自己ループはどのブロックにも含まれない
+    pub fn biconnected_component_edges(&self) -> &[Vec<usize>] {
+        &self.block_edges
+    }
+
+    // ブロックカット木の辺の列。頂点v(0 <= v < n)と、i番目のブロックを表す頂点n + iを、
+    // vがi番目のブロックに含まれるときに結ぶ。関節点以外の頂点は葉になる
+    pub fn block_cut_tree(&self) -> Vec<(usize, usize)> {
+        let n = self.size();
+        self.block_vertices
+            .iter()
+            .enumerate()
+            .flat_map(|(i, vertices)| vertices.iter().map(move |&v| (v, n + i)))
+            .collect()
+    }
+
+    // DFS木での親
+    fn parent(&self, v: usize) -> Option<usize> {
+        self.parent_edges[v].map(|id| {
+            let (a, b) = self.edges[id];
+            if a == v {
+                b
+            } else {
+                a
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_structure::union_find::UnionFind;
+    use proptest::prelude::*;
+    use rand::prelude::*;
+
+    #[test]
+    fn lowlink_test() {
+        //  0 - 1 - 3 - 4
+        //   \ /    |   |
+        //    2     6 - 5   7 = 8
+        let edges = vec![
+            (0, 1),
+            (1, 2),
+            (2, 0),
+            (1, 3),
+            (3, 4),
+            (4, 5),
+            (5, 6),
+            (6, 3),
+            (7, 8),
+            (8, 7),
+            (9, 9),
+        ];
+        let lowlink = LowLink::new(10, &edges);
+
+        assert_eq!(lowlink.bridges(), vec![3]);
+        assert!(lowlink.is_bridge(3));
+        assert!(!lowlink.is_bridge(8));
+        assert!(!lowlink.is_bridge(10));
+        assert_eq!(lowlink.articulation_points(), vec![1, 3]);
+        assert!(lowlink.is_articulation_point(1));
+        assert!(!lowlink.is_articulation_point(0));
+
+        assert_eq!(
+            lowlink.two_edge_connected_components(),
+            vec![vec![0, 1, 2], vec![3, 4, 5, 6], vec![7, 8], vec![9]]
+        );
+        assert_eq!(lowlink.bridge_tree(), vec![(0, 1, 3)]);
+
+        let mut blocks: Vec<Vec<usize>> = lowlink
+            .biconnected_components()
+            .iter()
+            .map(|block| {
+                let mut block = block.clone();
+                block.sort();
+                block
+            })
+            .collect();
+        blocks.sort();
+        assert_eq!(
+            blocks,
+            vec![
+                vec![0, 1, 2],
+                vec![1, 3],
+                vec![3, 4, 5, 6],
+                vec![7, 8],
+                vec![9]
+            ]
+        );
+        let block_cut_tree = lowlink.block_cut_tree();
+        assert_eq!(block_cut_tree.len(), 3 + 2 + 4 + 2 + 1);
+        assert_eq!(block_cut_tree.iter().filter(|&&(v, _)| v == 1).count(), 2);
+        assert_eq!(block_cut_tree.iter().filter(|&&(v, _)| v == 0).count(), 1);
+    }
+
+    // skip_edgeとskip_vertexを除いたときの連結成分
+    fn components(
+        n: usize,
+        edges: &[(usize, usize)],
+        skip_edge: Option<usize>,
+        skip_vertex: Option<usize>,
+    ) -> UnionFind {
+        let mut uf = UnionFind::new(n);
+        for (id, &(u, v)) in edges.iter().enumerate() {
+            if Some(id) != skip_edge && Some(u) != skip_vertex && Some(v) != skip_vertex {
+                uf.unite(u, v);
+            }
+        }
+        uf
+    }
+
+    proptest! {
+        #[test]
+        fn lowlink_random_test(n :u8, m :u8) {
+            let n = n as usize % 10 + 1;
+            let m = m as usize % 15;
+            let edges: Vec<(usize, usize)> = (0..m)
+                .map(|_| (random::<usize>() % n, random::<usize>() % n))
+                .collect();
+            let lowlink = LowLink::new(n, &edges);
+            let mut all = components(n, &edges, None, None);
+            let count = |uf: &mut UnionFind, skip: Option<usize>| {
+                (0..n).filter(|&v| Some(v) != skip && uf.root(v) == v).count()
+            };
+            let all_count = count(&mut all, None);
+
+            for (id, &(u, v)) in edges.iter().enumerate() {
+                let mut uf = components(n, &edges, Some(id), None);
+                prop_assert_eq!(lowlink.is_bridge(id), !uf.same(u, v));
+            }
+            let bridges: Vec<usize> = (0..m).filter(|&id| lowlink.is_bridge(id)).collect();
+            prop_assert_eq!(lowlink.bridges(), bridges);
+
+            let mut articulation_points = vec![];
+            for v in 0..n {
+                let mut uf = components(n, &edges, None, Some(v));
+                // vを含む成分が、vを取り除くと2つ以上に分かれる
+                let is_articulation_point = count(&mut uf, Some(v)) > all_count;
+                prop_assert_eq!(lowlink.is_articulation_point(v), is_articulation_point);
+                if is_articulation_point {
+                    articulation_points.push(v);
+                }
+            }
+            prop_assert_eq!(lowlink.articulation_points(), articulation_points);
+
+            // 同じ二重辺連結成分 <=> どの1辺を取り除いても連結
+            let (_, ids) = lowlink.two_edge_connected_component_ids();
+            for u in 0..n {
+                for v in 0..n {
+                    let expected = (0..m).all(|id| components(n, &edges, Some(id), None).same(u, v))
+                        && all.same(u, v);
+                    prop_assert_eq!(ids[u] == ids[v], expected);
+                }
+            }
+            for (a, b, id) in lowlink.bridge_tree() {
+                let (u, v) = edges[id];
+                prop_assert_eq!((a, b), (ids[u], ids[v]));
+            }
+
+            // 自己ループ以外の各辺はちょうど1つのブロックに含まれる
+            let mut blocks = vec![None; m];
+            for (i, block) in lowlink.biconnected_component_edges().iter().enumerate() {
+                for &id in block {
+                    prop_assert!(blocks[id].is_none());
+                    blocks[id] = Some(i);
+                }
+            }
+            // 同じブロック <=> どの1頂点を取り除いても、2辺の残った端点が連結
+            for e in 0..m {
+                for f in 0..m {
+                    if edges[e].0 == edges[e].1 || edges[f].0 == edges[f].1 {
+                        prop_assert_eq!(blocks[e].is_none(), edges[e].0 == edges[e].1);
+                        continue;
+                    }
+                    let other = |(u, v): (usize, usize), w: usize| if u == w { v } else { u };
+                    let expected = all.same(edges[e].0, edges[f].0)
+                        && (0..n).all(|w| {
+                            components(n, &edges, None, Some(w)).same(other(edges[e], w), other(edges[f], w))
+                        });
+                    prop_assert_eq!(blocks[e] == blocks[f], expected);
+                }
+            }
+            for (block, vertices) in lowlink
+                .biconnected_component_edges()
+                .iter()
+                .zip(lowlink.biconnected_components())
+            {
+                let mut expected: Vec<usize> = block.iter().flat_map(|&id| vec![edges[id].0, edges[id].1]).collect();
+                let mut vertices = vertices.clone();
+                expected.sort();
+                expected.dedup();
+                vertices.sort();
+                if block.is_empty() {
+                    prop_assert_eq!(vertices.len(), 1);
+                } else {
+                    prop_assert_eq!(vertices, expected);
+                }
+            }
+
+            // ブロックカット木は森で、連結成分の数は元のグラフと同じ
+            let block_count = lowlink.biconnected_components().len();
+            let mut uf = UnionFind::new(n + block_count);
+            for (v, block) in lowlink.block_cut_tree() {
+                prop_assert!(!uf.same(v, block));
+                uf.unite(v, block);
+            }
+            prop_assert_eq!(count(&mut uf, None), all_count);
+        }
+    }
+}
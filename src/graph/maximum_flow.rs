@@ -1,28 +1,48 @@
 use cargo_snippet::snippet;
 
+// Dinic法による最大流。容量は整数型
+// 辺はadd_edgeで追加した順に0から番号が振られる
 #[snippet("@MaximumFlow")]
-struct MaximumFlow {
-    graph: Vec<Vec<Edge>>,
+#[snippet("@BoundedFlow")]
+pub struct MaximumFlow<T> {
+    graph: Vec<Vec<Edge<T>>>,
+    // positions[id]: id番目の辺の(始点, graph[始点]内の位置)
+    positions: Vec<(usize, usize)>,
 }
 
 #[snippet("@MaximumFlow")]
-struct Edge {
+#[snippet("@BoundedFlow")]
+struct Edge<T> {
     to: usize,
     to_edge_index: usize,
-    capacity: i128,
+    // 残余容量
+    capacity: T,
 }
 
+// get_edgeで取得する辺の状態
 #[snippet("@MaximumFlow")]
+#[snippet("@BoundedFlow")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FlowEdge<T> {
+    pub from: usize,
+    pub to: usize,
+    pub capacity: T,
+    pub flow: T,
+}
 
-// Dinic法
-impl MaximumFlow {
-    fn new(n: usize) -> MaximumFlow {
-        let mut graph: Vec<Vec<Edge>> = Vec::new();
+#[snippet("@MaximumFlow")]
+#[snippet("@BoundedFlow")]
+impl<T: num::PrimInt> MaximumFlow<T> {
+    pub fn new(n: usize) -> MaximumFlow<T> {
+        let mut graph: Vec<Vec<Edge<T>>> = Vec::new();
         for _ in 0..n {
             graph.push(Vec::new());
         }
 
-        MaximumFlow { graph }
+        MaximumFlow {
+            graph,
+            positions: vec![],
+        }
     }
 
     fn bfs(&mut self, s: usize) -> Vec<i128> {
@@ -32,7 +52,7 @@ impl MaximumFlow {
         que.push_back(s);
         while let Some(v) = que.pop_front() {
             for e in self.graph[v].iter() {
-                if e.capacity > 0 && level[e.to] < 0 {
+                if e.capacity > T::zero() && level[e.to] < 0 {
                     level[e.to] = level[v] + 1;
                     que.push_back(e.to);
                 }
@@ -48,8 +68,8 @@ impl MaximumFlow {
         to: usize,
         level: &[i128],
         edge_used: &mut [usize],
-        flow: i128,
-    ) -> i128 {
+        flow: T,
+    ) -> T {
         if from == to {
             return flow;
         }
@@ -60,12 +80,14 @@ impl MaximumFlow {
             let flow = std::cmp::min(flow, self.graph[from][edge_index].capacity);
             let edge_to = self.graph[from][edge_index].to;
 
-            if flow > 0 && level[from] < level[edge_to] {
+            if flow > T::zero() && level[from] < level[edge_to] {
                 let child_flow = self.dfs(edge_to, to, level, edge_used, flow);
-                if child_flow > 0 {
+                if child_flow > T::zero() {
                     let rev_edge_index = self.graph[from][edge_index].to_edge_index;
-                    self.graph[from][edge_index].capacity -= child_flow;
-                    self.graph[edge_to][rev_edge_index].capacity += child_flow;
+                    self.graph[from][edge_index].capacity =
+                        self.graph[from][edge_index].capacity - child_flow;
+                    self.graph[edge_to][rev_edge_index].capacity =
+                        self.graph[edge_to][rev_edge_index].capacity + child_flow;
                     return child_flow;
                 }
             }
@@ -73,12 +95,15 @@ impl MaximumFlow {
             edge_used[from] += 1;
         }
 
-        0
+        T::zero()
     }
 
-    pub fn add_edge(&mut self, from: usize, to: usize, capacity: i128) {
-        let to_len = self.graph[to].len();
+    // from -> toの辺を追加し、辺の番号を返す
+    pub fn add_edge(&mut self, from: usize, to: usize, capacity: T) -> usize {
+        assert!(capacity >= T::zero());
+        let to_len = self.graph[to].len() + if from == to { 1 } else { 0 };
         let from_len = self.graph[from].len();
+        self.positions.push((from, from_len));
         self.graph[from].push(Edge {
             to,
             to_edge_index: to_len,
@@ -89,33 +114,94 @@ impl MaximumFlow {
         self.graph[to].push(Edge {
             to: from,
             to_edge_index: from_len,
-            capacity: 0,
+            capacity: T::zero(),
         });
+
+        self.positions.len() - 1
+    }
+
+    // id番目の辺の容量と、現在流れている流量
+    pub fn get_edge(&self, id: usize) -> FlowEdge<T> {
+        let (from, index) = self.positions[id];
+        let edge = &self.graph[from][index];
+        let rev_edge = &self.graph[edge.to][edge.to_edge_index];
+
+        FlowEdge {
+            from,
+            to: edge.to,
+            capacity: edge.capacity + rev_edge.capacity,
+            flow: rev_edge.capacity,
+        }
+    }
+
+    // 全ての辺の状態。add_edgeで追加した順に並ぶ
+    pub fn edges(&self) -> Vec<FlowEdge<T>> {
+        (0..self.positions.len())
+            .map(|id| self.get_edge(id))
+            .collect()
     }
 
-    pub fn maximum_flow(&mut self, from: usize, to: usize) -> i128 {
-        let mut flow: i128 = 0;
+    // id番目の辺の容量をcapacity、流量をflowに変更する。他の辺の流量は変わらないので、流量保存則が崩れうる
+    pub fn change_capacity(&mut self, id: usize, capacity: T, flow: T) {
+        assert!(T::zero() <= flow && flow <= capacity);
+        let (from, index) = self.positions[id];
+        let (to, rev_index) = {
+            let edge = &self.graph[from][index];
+            (edge.to, edge.to_edge_index)
+        };
+        self.graph[from][index].capacity = capacity - flow;
+        self.graph[to][rev_index].capacity = flow;
+    }
+
+    pub fn maximum_flow(&mut self, from: usize, to: usize) -> T {
+        self.maximum_flow_with_limit(from, to, T::max_value())
+    }
 
-        loop {
+    // 流量がlimitに達したら打ち切る
+    pub fn maximum_flow_with_limit(&mut self, from: usize, to: usize, limit: T) -> T {
+        let mut flow = T::zero();
+
+        while flow < limit {
             let level = self.bfs(from);
             if level[to] < 0 {
-                return flow;
+                break;
             }
             let mut edge_used = vec![0; self.graph.len()];
-            loop {
-                let one_path_flow = self.dfs(from, to, &level, &mut edge_used, std::i128::MAX);
-                if one_path_flow == 0 {
+            while flow < limit {
+                let one_path_flow = self.dfs(from, to, &level, &mut edge_used, limit - flow);
+                if one_path_flow == T::zero() {
                     break;
                 }
-                flow += one_path_flow;
+                flow = flow + one_path_flow;
+            }
+        }
+
+        flow
+    }
+
+    // 残余グラフでfromから到達できる頂点。maximum_flowの後に呼ぶと、trueの頂点の集合が最小カットのfrom側になる
+    pub fn min_cut(&self, from: usize) -> Vec<bool> {
+        let mut visited = vec![false; self.graph.len()];
+        visited[from] = true;
+        let mut stack = vec![from];
+        while let Some(v) = stack.pop() {
+            for e in self.graph[v].iter() {
+                if e.capacity > T::zero() && !visited[e.to] {
+                    visited[e.to] = true;
+                    stack.push(e.to);
+                }
             }
         }
+
+        visited
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use proptest::prelude::*;
+    use rand::prelude::*;
 
     #[test]
     fn maxflow_test() {
@@ -138,4 +224,92 @@ mod test {
         let flow = graph.maximum_flow(0, 3);
         assert_eq!(flow, 10);
     }
+
+    #[test]
+    fn maxflow_edges_test() {
+        let mut graph = MaximumFlow::<u32>::new(4);
+        let a = graph.add_edge(0, 1, 3);
+        let b = graph.add_edge(0, 2, 2);
+        let c = graph.add_edge(1, 2, 5);
+        let d = graph.add_edge(1, 3, 1);
+        let e = graph.add_edge(2, 3, 3);
+        graph.add_edge(3, 3, 7);
+        assert_eq!((a, b, c, d, e), (0, 1, 2, 3, 4));
+
+        assert_eq!(graph.maximum_flow_with_limit(0, 3, 2), 2);
+        assert_eq!(graph.maximum_flow(0, 3), 2);
+        assert_eq!(
+            graph.get_edge(d),
+            FlowEdge {
+                from: 1,
+                to: 3,
+                capacity: 1,
+                flow: 1
+            }
+        );
+        assert_eq!(graph.get_edge(e).flow, 3);
+        assert_eq!(graph.get_edge(5).flow, 0);
+        let edges = graph.edges();
+        assert_eq!(edges[a].flow + edges[b].flow, 4);
+
+        // 1 -> 3, 2 -> 3 が最小カット
+        assert_eq!(graph.min_cut(0), vec![true, true, true, false]);
+
+        graph.change_capacity(d, 3, 1);
+        assert_eq!(graph.maximum_flow(0, 3), 1);
+        assert_eq!(graph.get_edge(d).flow, 2);
+        assert_eq!(graph.min_cut(0), vec![true, false, false, false]);
+    }
+
+    proptest! {
+        #[test]
+        fn maxflow_random_test(n :u8) {
+            let n = n as usize % 6 + 2;
+            let mut graph = MaximumFlow::<i64>::new(n);
+            let mut edges = vec![];
+            for _ in 0..n * 2 {
+                let edge = (random::<usize>() % n, random::<usize>() % n, random::<i64>().abs() % 10);
+                graph.add_edge(edge.0, edge.1, edge.2);
+                edges.push(edge);
+            }
+
+            let flow = graph.maximum_flow(0, n - 1);
+
+            // 各辺の流量が容量以下で、流量保存則を満たす
+            let mut balance = vec![0; n];
+            for (id, &(from, to, capacity)) in edges.iter().enumerate() {
+                let edge = graph.get_edge(id);
+                prop_assert_eq!((edge.from, edge.to, edge.capacity), (from, to, capacity));
+                prop_assert!(0 <= edge.flow && edge.flow <= capacity);
+                balance[from] -= edge.flow;
+                balance[to] += edge.flow;
+            }
+            prop_assert_eq!(balance[0], -flow);
+            prop_assert_eq!(balance[n - 1], flow);
+            prop_assert!((1..n - 1).all(|v| balance[v] == 0));
+
+            // 最小カットの容量が最大流と一致する
+            let cut = graph.min_cut(0);
+            prop_assert!(!cut[n - 1]);
+            let cut_capacity: i64 = edges
+                .iter()
+                .filter(|&&(from, to, _)| cut[from] && !cut[to])
+                .map(|e| e.2)
+                .sum();
+            prop_assert_eq!(cut_capacity, flow);
+
+            // 全ての部分集合のカットの容量以下
+            for mask in 0..1usize << n {
+                if mask & 1 == 0 || mask >> (n - 1) & 1 == 1 {
+                    continue;
+                }
+                let capacity: i64 = edges
+                    .iter()
+                    .filter(|&&(from, to, _)| mask >> from & 1 == 1 && mask >> to & 1 == 0)
+                    .map(|e| e.2)
+                    .sum();
+                prop_assert!(flow <= capacity);
+            }
+        }
+    }
 }
@@ -0,0 +1,364 @@
+use cargo_snippet::snippet;
+
+// 最小費用流。ポテンシャルを用いたDijkstra法で最短路を繰り返し求める(主双対法)
+// 負のコストの辺があれば最初にBellman-Ford法でポテンシャルを求める。ただし負閉路があってはならない
+// 辺はadd_edgeで追加した順に0から番号が振られる
+#[snippet("@MinCostFlow")]
+pub struct MinCostFlow<T> {
+    graph: Vec<Vec<Edge<T>>>,
+    // positions[id]: id番目の辺の(始点, graph[始点]内の位置)
+    positions: Vec<(usize, usize)>,
+}
+
+#[snippet("@MinCostFlow")]
+struct Edge<T> {
+    to: usize,
+    to_edge_index: usize,
+    // 残余容量
+    capacity: T,
+    cost: T,
+}
+
+// get_edgeで取得する辺の状態
+#[snippet("@MinCostFlow")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MinCostFlowEdge<T> {
+    pub from: usize,
+    pub to: usize,
+    pub capacity: T,
+    pub flow: T,
+    pub cost: T,
+}
+
+#[snippet("@MinCostFlow")]
+impl<T: num::PrimInt + num::Signed> MinCostFlow<T> {
+    pub fn new(n: usize) -> MinCostFlow<T> {
+        let mut graph: Vec<Vec<Edge<T>>> = Vec::new();
+        for _ in 0..n {
+            graph.push(Vec::new());
+        }
+
+        MinCostFlow {
+            graph,
+            positions: vec![],
+        }
+    }
+
+    // from -> toに容量capacity、単位流量あたりのコストcostの辺を追加し、辺の番号を返す
+    pub fn add_edge(&mut self, from: usize, to: usize, capacity: T, cost: T) -> usize {
+        assert!(capacity >= T::zero());
+        let to_len = self.graph[to].len() + if from == to { 1 } else { 0 };
+        let from_len = self.graph[from].len();
+        self.positions.push((from, from_len));
+        self.graph[from].push(Edge {
+            to,
+            to_edge_index: to_len,
+            capacity,
+            cost,
+        });
+
+        // 逆辺
+        self.graph[to].push(Edge {
+            to: from,
+            to_edge_index: from_len,
+            capacity: T::zero(),
+            cost: -cost,
+        });
+
+        self.positions.len() - 1
+    }
+
+    // id番目の辺の容量、コストと、現在流れている流量
+    pub fn get_edge(&self, id: usize) -> MinCostFlowEdge<T> {
+        let (from, index) = self.positions[id];
+        let edge = &self.graph[from][index];
+        let rev_edge = &self.graph[edge.to][edge.to_edge_index];
+
+        MinCostFlowEdge {
+            from,
+            to: edge.to,
+            capacity: edge.capacity + rev_edge.capacity,
+            flow: rev_edge.capacity,
+            cost: edge.cost,
+        }
+    }
+
+    // 全ての辺の状態。add_edgeで追加した順に並ぶ
+    pub fn edges(&self) -> Vec<MinCostFlowEdge<T>> {
+        (0..self.positions.len())
+            .map(|id| self.get_edge(id))
+            .collect()
+    }
+
+    // 残余グラフでfromからの最短距離をBellman-Ford法で求め、初期ポテンシャルにする O(VE)
+    // 到達できない頂点のポテンシャルは0
+    fn initial_potentials(&self, from: usize) -> Vec<T> {
+        let has_negative_edge = self
+            .graph
+            .iter()
+            .flatten()
+            .any(|e| e.capacity > T::zero() && e.cost < T::zero());
+        if !has_negative_edge {
+            return vec![T::zero(); self.graph.len()];
+        }
+
+        let mut dists: Vec<Option<T>> = vec![None; self.graph.len()];
+        dists[from] = Some(T::zero());
+        for round in 0..=self.graph.len() {
+            let mut updated = false;
+            for (v, v_edges) in self.graph.iter().enumerate() {
+                let dist = match dists[v] {
+                    Some(dist) => dist,
+                    None => continue,
+                };
+                for e in v_edges.iter().filter(|e| e.capacity > T::zero()) {
+                    if dists[e.to].is_none() || Some(dist + e.cost) < dists[e.to] {
+                        dists[e.to] = Some(dist + e.cost);
+                        updated = true;
+                    }
+                }
+            }
+            if !updated {
+                break;
+            }
+            assert!(round < self.graph.len(), "negative cycle exists");
+        }
+
+        dists.iter().map(|dist| dist.unwrap_or(T::zero())).collect()
+    }
+
+    // fromからtoへ流量limitまで流したときの、流量とコストの関係を表す折れ線の頂点
+    // (0, 0)から始まり、流量の昇順に並ぶ。傾きが変わる点だけを含む
+    pub fn slope(&mut self, from: usize, to: usize, limit: T) -> Vec<(T, T)> {
+        assert_ne!(from, to);
+        let n = self.graph.len();
+        let mut potentials = self.initial_potentials(from);
+        let mut result = vec![(T::zero(), T::zero())];
+        let mut flow = T::zero();
+        let mut cost = T::zero();
+        let mut prev_cost_per_flow = None;
+
+        while flow < limit {
+            // 辺のコストをポテンシャルで補正すると非負になるので、Dijkstra法が使える
+            let mut dists = vec![T::max_value(); n];
+            // prev[v]: vへの最短路の最後の辺の(始点, graph[始点]内の位置)
+            let mut prev = vec![(usize::MAX, usize::MAX); n];
+            let mut queue: std::collections::BinaryHeap<std::cmp::Reverse<(T, usize)>> =
+                std::collections::BinaryHeap::new();
+            dists[from] = T::zero();
+            queue.push(std::cmp::Reverse((T::zero(), from)));
+            while let Some(std::cmp::Reverse((dist, v))) = queue.pop() {
+                if dists[v] < dist {
+                    continue;
+                }
+                for (index, e) in self.graph[v].iter().enumerate() {
+                    if e.capacity == T::zero() {
+                        continue;
+                    }
+                    let new_dist = dist + e.cost + potentials[v] - potentials[e.to];
+                    if new_dist < dists[e.to] {
+                        dists[e.to] = new_dist;
+                        prev[e.to] = (v, index);
+                        queue.push(std::cmp::Reverse((new_dist, e.to)));
+                    }
+                }
+            }
+            if dists[to] == T::max_value() {
+                break;
+            }
+
+            for v in 0..n {
+                if dists[v] != T::max_value() {
+                    potentials[v] = potentials[v] + dists[v];
+                }
+            }
+
+            // 最短路上の残余容量の最小値だけ流す
+            let mut path_flow = limit - flow;
+            let mut v = to;
+            while v != from {
+                let (prev_v, index) = prev[v];
+                path_flow = std::cmp::min(path_flow, self.graph[prev_v][index].capacity);
+                v = prev_v;
+            }
+            let mut v = to;
+            while v != from {
+                let (prev_v, index) = prev[v];
+                let rev_index = self.graph[prev_v][index].to_edge_index;
+                self.graph[prev_v][index].capacity = self.graph[prev_v][index].capacity - path_flow;
+                self.graph[v][rev_index].capacity = self.graph[v][rev_index].capacity + path_flow;
+                v = prev_v;
+            }
+
+            let cost_per_flow = potentials[to] - potentials[from];
+            flow = flow + path_flow;
+            cost = cost + path_flow * cost_per_flow;
+            if prev_cost_per_flow == Some(cost_per_flow) {
+                result.pop();
+            }
+            result.push((flow, cost));
+            prev_cost_per_flow = Some(cost_per_flow);
+        }
+
+        result
+    }
+
+    // fromからtoへ流量limitまで流したときの(流量, 最小コスト)
+    pub fn flow(&mut self, from: usize, to: usize, limit: T) -> (T, T) {
+        *self.slope(from, to, limit).last().unwrap()
+    }
+
+    // fromからtoへちょうどflowだけ流すときの最小コスト
+    // 流せなければNoneを返し、流し始める前の状態に戻す
+    pub fn min_cost_flow(&mut self, from: usize, to: usize, flow: T) -> Option<T> {
+        let capacities: Vec<Vec<T>> = self
+            .graph
+            .iter()
+            .map(|edges| edges.iter().map(|edge| edge.capacity).collect())
+            .collect();
+        let (max_flow, cost) = self.flow(from, to, flow);
+        if max_flow == flow {
+            return Some(cost);
+        }
+
+        for (edges, capacities) in self.graph.iter_mut().zip(capacities) {
+            for (edge, capacity) in edges.iter_mut().zip(capacities) {
+                edge.capacity = capacity;
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::graph::maximum_flow::MaximumFlow;
+    use crate::graph::warshall_floyd::WarshallFloyd;
+    use proptest::prelude::*;
+    use rand::prelude::*;
+
+    #[test]
+    fn min_cost_flow_test() {
+        let mut graph = MinCostFlow::<i64>::new(4);
+        let a = graph.add_edge(0, 1, 2, 1);
+        let b = graph.add_edge(0, 2, 1, 2);
+        let c = graph.add_edge(1, 2, 1, 1);
+        let d = graph.add_edge(1, 3, 1, 3);
+        let e = graph.add_edge(2, 3, 2, 1);
+
+        // 0->1->2->3 (3), 0->2->3 (3), 0->1->3 (4)
+        assert_eq!(graph.slope(0, 3, 10), vec![(0, 0), (2, 6), (3, 10)]);
+        assert_eq!(graph.get_edge(a).flow, 2);
+        assert_eq!(graph.get_edge(b).flow, 1);
+        assert_eq!(graph.get_edge(c).flow, 1);
+        assert_eq!(
+            graph.get_edge(d),
+            MinCostFlowEdge {
+                from: 1,
+                to: 3,
+                capacity: 1,
+                flow: 1,
+                cost: 3
+            }
+        );
+        assert_eq!(graph.get_edge(e).flow, 2);
+
+        let mut graph = MinCostFlow::<i64>::new(4);
+        graph.add_edge(0, 1, 2, 1);
+        graph.add_edge(0, 2, 1, 2);
+        graph.add_edge(1, 2, 1, 1);
+        graph.add_edge(1, 3, 1, 3);
+        graph.add_edge(2, 3, 2, 1);
+        assert_eq!(graph.min_cost_flow(0, 3, 4), None);
+        // 流せなかった場合は何も流していない状態に戻る
+        assert!(graph.edges().iter().all(|edge| edge.flow == 0));
+        assert_eq!(graph.min_cost_flow(0, 3, 3), Some(10));
+
+        let mut graph = MinCostFlow::<i64>::new(4);
+        graph.add_edge(0, 1, 2, 1);
+        graph.add_edge(0, 2, 1, 2);
+        graph.add_edge(1, 2, 1, 1);
+        graph.add_edge(1, 3, 1, 3);
+        graph.add_edge(2, 3, 2, 1);
+        assert_eq!(graph.min_cost_flow(0, 3, 1), Some(3));
+        // 続けて流せる
+        assert_eq!(graph.min_cost_flow(0, 3, 2), Some(7));
+    }
+
+    #[test]
+    fn min_cost_flow_negative_cost_test() {
+        // 負のコストの辺を含む
+        let mut graph = MinCostFlow::<i32>::new(4);
+        graph.add_edge(0, 1, 1, 5);
+        graph.add_edge(1, 2, 2, -7);
+        graph.add_edge(0, 2, 2, 1);
+        graph.add_edge(2, 3, 3, 2);
+        graph.add_edge(3, 0, 1, 1);
+
+        assert_eq!(graph.slope(0, 3, 5), vec![(0, 0), (1, 0), (3, 6)]);
+        assert_eq!(graph.edges().iter().map(|e| e.flow).sum::<i32>(), 7);
+    }
+
+    proptest! {
+        #[test]
+        fn min_cost_flow_random_test(n :u8) {
+            let n = n as usize % 6 + 2;
+            let mut graph = MinCostFlow::<i64>::new(n);
+            let mut maximum_flow = MaximumFlow::<i64>::new(n);
+            let mut edges = vec![];
+            // 辺のコストをc + potentials[from] - potentials[to] (c >= 0)とすると、負閉路ができない
+            let potentials: Vec<i64> = (0..n).map(|_| random::<i64>() % 10).collect();
+            for _ in 0..n * 2 {
+                let from = random::<usize>() % n;
+                let to = random::<usize>() % n;
+                let capacity = random::<i64>().abs() % 5;
+                let cost = random::<i64>().abs() % 10 + potentials[from] - potentials[to];
+                graph.add_edge(from, to, capacity, cost);
+                maximum_flow.add_edge(from, to, capacity);
+                edges.push((from, to, capacity, cost));
+            }
+
+            let slope = graph.slope(0, n - 1, i64::MAX);
+            let (flow, cost) = *slope.last().unwrap();
+            prop_assert_eq!(flow, maximum_flow.maximum_flow(0, n - 1));
+
+            // 折れ線は下に凸
+            for i in 1..slope.len() {
+                prop_assert!(slope[i - 1].0 < slope[i].0);
+                if i >= 2 {
+                    prop_assert!(
+                        (slope[i - 1].1 - slope[i - 2].1) * (slope[i].0 - slope[i - 1].0)
+                            < (slope[i].1 - slope[i - 1].1) * (slope[i - 1].0 - slope[i - 2].0)
+                    );
+                }
+            }
+
+            // 各辺の流量が容量以下で、流量保存則を満たし、コストの合計が一致する
+            let mut balance = vec![0; n];
+            let mut total_cost = 0;
+            let mut residual = WarshallFloyd::new(n);
+            for (id, &(from, to, capacity, edge_cost)) in edges.iter().enumerate() {
+                let edge = graph.get_edge(id);
+                prop_assert_eq!((edge.from, edge.to, edge.capacity, edge.cost), (from, to, capacity, edge_cost));
+                prop_assert!(0 <= edge.flow && edge.flow <= capacity);
+                balance[from] -= edge.flow;
+                balance[to] += edge.flow;
+                total_cost += edge.flow * edge_cost;
+                if edge.flow < capacity {
+                    residual.add_edge(from, to, edge_cost);
+                }
+                if edge.flow > 0 {
+                    residual.add_edge(to, from, -edge_cost);
+                }
+            }
+            prop_assert_eq!(total_cost, cost);
+            prop_assert_eq!(balance[0], -flow);
+            prop_assert_eq!(balance[n - 1], flow);
+            prop_assert!((1..n - 1).all(|v| balance[v] == 0));
+
+            // 残余グラフに負閉路がなければ、その流量での最小費用流になっている
+            prop_assert!(!residual.has_negative_cycle());
+        }
+    }
+}
@@ -1,4 +1,19 @@
+pub mod bipartite_matching;
+pub mod bounded_flow;
+pub mod centroid_decomposition;
+pub mod csr;
+pub mod distance;
 pub mod graph;
+pub mod heavy_light_decomposition;
+pub mod hungarian;
+pub mod lowlink;
 pub mod maximum_flow;
+pub mod min_cost_flow;
+pub mod rerooting;
+pub mod scc;
+pub mod shortest_path_tree;
 pub mod simple_graph;
 pub mod toporogical_sort;
+pub mod tree;
+pub mod two_sat;
+pub mod warshall_floyd;
@@ -0,0 +1,156 @@
+use crate::graph::csr::Csr;
+use cargo_snippet::snippet;
+
+// 全方位木DP。全ての頂点について、その頂点を根としたときの木DPの値を求める O(n)
+// 根vの値は add_vertex(merge(add_edge(子cの値, 辺v-cのコスト), ...), v) で定める
+// mergeは結合的かつ可換で、identityはその単位元
+// graphは無向辺を両方向に含む森の隣接リスト。Graphから使う場合はgraph.csr()を渡す
+#[snippet("@rerooting")]
+pub fn rerooting<T: Clone, E: Copy>(
+    graph: &Csr<E>,
+    identity: T,
+    merge: fn(T, T) -> T,
+    add_edge: fn(T, E) -> T,
+    add_vertex: fn(T, usize) -> T,
+) -> Vec<T> {
+    let n = graph.size();
+    let mut parents = vec![None; n];
+    let mut visited = vec![false; n];
+    let mut order = Vec::with_capacity(n);
+    for root in 0..n {
+        if visited[root] {
+            continue;
+        }
+        visited[root] = true;
+        let start = order.len();
+        order.push(root);
+        let mut index = start;
+        while index < order.len() {
+            let v = order[index];
+            index += 1;
+            for &(to, cost) in graph.adjacent(v) {
+                if !visited[to] {
+                    visited[to] = true;
+                    parents[to] = Some((v, cost));
+                    order.push(to);
+                }
+            }
+        }
+    }
+
+    // downs[v]: vの部分木だけで求めたvの値
+    let mut children_values = vec![identity.clone(); n];
+    let mut downs = vec![identity.clone(); n];
+    for &v in order.iter().rev() {
+        downs[v] = add_vertex(children_values[v].clone(), v);
+        if let Some((parent, cost)) = parents[v] {
+            children_values[parent] = merge(
+                children_values[parent].clone(),
+                add_edge(downs[v].clone(), cost),
+            );
+        }
+    }
+
+    // ups[v]: vの親を根として、vの部分木を除いた木で求めた親の値
+    let mut ups: Vec<Option<T>> = vec![None; n];
+    let mut result = vec![identity.clone(); n];
+    for &v in &order {
+        // vに隣接する各頂点の側の値
+        let values: Vec<T> = graph
+            .adjacent(v)
+            .iter()
+            .map(|&(to, cost)| match parents[v] {
+                Some((parent, _)) if parent == to => add_edge(ups[v].clone().unwrap(), cost),
+                _ => add_edge(downs[to].clone(), cost),
+            })
+            .collect();
+
+        // suffixes[i]: values[i..]をまとめた値
+        let mut suffixes = vec![identity.clone(); values.len() + 1];
+        for i in (0..values.len()).rev() {
+            suffixes[i] = merge(values[i].clone(), suffixes[i + 1].clone());
+        }
+        result[v] = add_vertex(suffixes[0].clone(), v);
+
+        let mut prefix = identity.clone();
+        for (i, &(to, _)) in graph.adjacent(v).iter().enumerate() {
+            if matches!(parents[to], Some((parent, _)) if parent == v) {
+                ups[to] = Some(add_vertex(
+                    merge(prefix.clone(), suffixes[i + 1].clone()),
+                    v,
+                ));
+            }
+            prefix = merge(prefix, values[i].clone());
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::graph::Graph;
+    use crate::graph::tree::Tree;
+    use proptest::prelude::*;
+    use rand::prelude::*;
+
+    #[test]
+    fn rerooting_test() {
+        // 0-1-2, 1-3 の木で、各頂点から最も遠い頂点までの辺の数
+        let mut graph = Graph::new(4, false);
+        graph.add_edge(0, 1, 1usize);
+        graph.add_edge(1, 2, 1);
+        graph.add_edge(1, 3, 1);
+
+        let result = rerooting(
+            graph.csr(),
+            0usize,
+            |a, b| std::cmp::max(a, b),
+            |value, cost| value + cost,
+            |value, _| value,
+        );
+        assert_eq!(result, vec![2, 1, 2, 2]);
+
+        // 森でも各木ごとに求まる。頂点数を数える
+        let mut graph = Graph::new(5, false);
+        graph.add_edge(0, 3, 1usize);
+        graph.add_edge(4, 3, 1);
+        let result = rerooting(
+            graph.csr(),
+            0usize,
+            |a, b| a + b,
+            |value, _| value,
+            |value, _| value + 1,
+        );
+        assert_eq!(result, vec![3, 1, 1, 3, 3]);
+    }
+
+    proptest! {
+        #[test]
+        fn rerooting_random_test(n :u8) {
+            let n = n as usize % 50 + 1;
+            let edges: Vec<(usize, usize, i64)> = (1..n)
+                .map(|v| (random::<usize>() % v, v, random::<i64>() % 100))
+                .collect();
+            let mut graph = Graph::new(n, false);
+            for &(u, v, cost) in &edges {
+                graph.add_edge(u, v, cost);
+            }
+            let tree = Tree::new(n, &edges, 0);
+
+            // 他の全ての頂点への距離の和: (距離の和, 頂点数)
+            let result = rerooting(
+                graph.csr(),
+                (0i64, 0i64),
+                |a, b| (a.0 + b.0, a.1 + b.1),
+                |value, cost| (value.0 + value.1 * cost, value.1),
+                |value, _| (value.0, value.1 + 1),
+            );
+            for v in 0..n {
+                let expected: i64 = (0..n).map(|w| tree.weighted_dist(v, w)).sum();
+                prop_assert_eq!(result[v], (expected, n as i64));
+            }
+        }
+    }
+}
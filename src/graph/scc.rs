@@ -0,0 +1,103 @@
+use crate::graph::csr::Csr;
+use cargo_snippet::snippet;
+
+// 強連結成分分解(Tarjan法)。(成分数, 各頂点の成分番号)を返す O(n+m)
+// 成分番号はトポロジカル順で、uからvに到達できるならids[u] <= ids[v]
+// 長いパスでもスタックが溢れないよう、DFSは明示的なスタックで行う
+#[snippet("@scc_ids")]
+#[snippet("@Graph")]
+#[snippet("@SimpleGraph")]
+#[snippet("@TwoSat")]
+pub fn scc_ids<T: Copy>(graph: &Csr<T>) -> (usize, Vec<usize>) {
+    let n = graph.size();
+    let mut now_ord = 0;
+    let mut group_num = 0;
+    // まだ成分が決まっていない頂点
+    let mut visited = Vec::with_capacity(n);
+    let mut low = vec![0; n];
+    // 未訪問ならusize::MAX、成分が決まった頂点はn
+    let mut ord = vec![usize::MAX; n];
+    let mut ids = vec![0; n];
+
+    for root in 0..n {
+        if ord[root] != usize::MAX {
+            continue;
+        }
+        low[root] = now_ord;
+        ord[root] = now_ord;
+        now_ord += 1;
+        visited.push(root);
+
+        // (頂点, 次に見る隣接辺の添字)
+        let mut stack = vec![(root, 0)];
+        while let Some(&mut (v, ref mut index)) = stack.last_mut() {
+            if let Some(&(to, _)) = graph.adjacent(v).get(*index) {
+                *index += 1;
+                if ord[to] == usize::MAX {
+                    low[to] = now_ord;
+                    ord[to] = now_ord;
+                    now_ord += 1;
+                    visited.push(to);
+                    stack.push((to, 0));
+                } else {
+                    low[v] = std::cmp::min(low[v], ord[to]);
+                }
+                continue;
+            }
+
+            stack.pop();
+            if low[v] == ord[v] {
+                loop {
+                    let u = visited.pop().unwrap();
+                    ord[u] = n;
+                    ids[u] = group_num;
+                    if u == v {
+                        break;
+                    }
+                }
+                group_num += 1;
+            }
+            if let Some(&(parent, _)) = stack.last() {
+                low[parent] = std::cmp::min(low[parent], low[v]);
+            }
+        }
+    }
+
+    for x in ids.iter_mut() {
+        *x = group_num - 1 - *x;
+    }
+    (group_num, ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scc_ids_test() {
+        // 0 -> 1 -> 2 -> 0, 2 -> 3, 4は孤立
+        let csr = Csr::new(5, &[(0, 1, ()), (1, 2, ()), (2, 0, ()), (2, 3, ())]);
+        let (count, ids) = scc_ids(&csr);
+        assert_eq!(count, 3);
+        assert_eq!(ids[0], ids[1]);
+        assert_eq!(ids[1], ids[2]);
+        assert!(ids[2] < ids[3]);
+        assert_ne!(ids[4], ids[0]);
+        assert_ne!(ids[4], ids[3]);
+    }
+
+    #[test]
+    fn scc_ids_long_path_test() {
+        // 再帰で実装するとスタックが溢れる長さの閉路
+        let n = 1_000_000;
+        let edges: Vec<(usize, usize, ())> = (0..n).map(|v| (v, (v + 1) % n, ())).collect();
+        let (count, ids) = scc_ids(&Csr::new(n, &edges));
+        assert_eq!(count, 1);
+        assert!(ids.iter().all(|&id| id == 0));
+
+        let edges: Vec<(usize, usize, ())> = (1..n).map(|v| (v - 1, v, ())).collect();
+        let (count, ids) = scc_ids(&Csr::new(n, &edges));
+        assert_eq!(count, n);
+        assert!((0..n).all(|v| ids[v] == v));
+    }
+}
@@ -0,0 +1,128 @@
+use cargo_snippet::snippet;
+
+// 1点からの最短距離と、最短路における各頂点の1つ前の頂点・辺
+// 辺の番号はGraphではadd_edgeで追加した順番、SimpleGraphではcsrの辺の番号で、どちらもedge(id)で辺を得られる
+#[snippet("@ShortestPathTree")]
+#[snippet("@Graph")]
+#[snippet("@SimpleGraph")]
+#[derive(Clone, Debug)]
+pub struct ShortestPathTree<T> {
+    source: usize,
+    dists: Vec<T>,
+    prev_nodes: Vec<Option<usize>>,
+    prev_edges: Vec<Option<usize>>,
+}
+
+#[snippet("@ShortestPathTree")]
+#[snippet("@Graph")]
+#[snippet("@SimpleGraph")]
+impl<T: Copy> ShortestPathTree<T> {
+    pub fn new(
+        source: usize,
+        dists: Vec<T>,
+        prev_nodes: Vec<Option<usize>>,
+        prev_edges: Vec<Option<usize>>,
+    ) -> ShortestPathTree<T> {
+        ShortestPathTree {
+            source,
+            dists,
+            prev_nodes,
+            prev_edges,
+        }
+    }
+
+    pub fn source(&self) -> usize {
+        self.source
+    }
+
+    // 各頂点への最短距離。値の意味は求めたメソッド(min_dists等)と同じ
+    pub fn dists(&self) -> &[T] {
+        &self.dists
+    }
+
+    // 最短路でvの1つ前の頂点
+    pub fn prev_node(&self, v: usize) -> Option<usize> {
+        self.prev_nodes[v]
+    }
+
+    // 最短路でvに入る辺の番号
+    pub fn prev_edge(&self, v: usize) -> Option<usize> {
+        self.prev_edges[v]
+    }
+
+    // sourceからtargetへの最短路の頂点列(両端を含む)。到達できなければNone
+    // target == sourceの場合、sourceに戻る閉路が求まっていればその閉路、そうでなければ[source]
+    pub fn path_to(&self, target: usize) -> Option<Vec<usize>> {
+        if self.prev_nodes[target].is_none() {
+            return if target == self.source {
+                Some(vec![target])
+            } else {
+                None
+            };
+        }
+
+        let mut path = vec![target];
+        let mut v = target;
+        loop {
+            v = self.prev_nodes[v].unwrap();
+            path.push(v);
+            if v == self.source {
+                break;
+            }
+        }
+        path.reverse();
+
+        Some(path)
+    }
+
+    // sourceからtargetへの最短路の辺番号の列。到達できなければNone
+    pub fn edge_path_to(&self, target: usize) -> Option<Vec<usize>> {
+        if self.prev_nodes[target].is_none() {
+            return if target == self.source {
+                Some(vec![])
+            } else {
+                None
+            };
+        }
+
+        let mut path = vec![];
+        let mut v = target;
+        loop {
+            path.push(self.prev_edges[v].unwrap());
+            v = self.prev_nodes[v].unwrap();
+            if v == self.source {
+                break;
+            }
+        }
+        path.reverse();
+
+        Some(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shortest_path_tree_test() {
+        // 0 -> 1 -> 2, 0 -> 3, 4は到達不能
+        let tree = ShortestPathTree::new(
+            0,
+            vec![0, 1, 3, 5, std::usize::MAX],
+            vec![None, Some(0), Some(1), Some(0), None],
+            vec![None, Some(10), Some(11), Some(12), None],
+        );
+
+        assert_eq!(tree.source(), 0);
+        assert_eq!(tree.dists(), &[0, 1, 3, 5, std::usize::MAX]);
+        assert_eq!(tree.prev_node(2), Some(1));
+        assert_eq!(tree.prev_edge(2), Some(11));
+        assert_eq!(tree.path_to(2), Some(vec![0, 1, 2]));
+        assert_eq!(tree.edge_path_to(2), Some(vec![10, 11]));
+        assert_eq!(tree.path_to(0), Some(vec![0]));
+        assert_eq!(tree.edge_path_to(0), Some(vec![]));
+        assert_eq!(tree.path_to(4), None);
+        assert_eq!(tree.edge_path_to(4), None);
+    }
+}
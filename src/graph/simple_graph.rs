@@ -1,3 +1,8 @@
+use crate::data_structure::radix_heap::RadixHeap;
+use crate::graph::csr::Csr;
+use crate::graph::distance::Distance;
+use crate::graph::scc::scc_ids;
+use crate::graph::shortest_path_tree::ShortestPathTree;
 use cargo_snippet::snippet;
 
 // 多重辺を含むグラフ。頂点は0-indexed。自己ループ辺もok
@@ -6,6 +11,8 @@ pub struct SimpleGraph<T> {
     size: usize,
     edges: Vec<Vec<(usize, T)>>,
     directed: bool,
+    // edgesから作る隣接リスト。最初に必要になった時に作り、辺の追加・削除で破棄する
+    csr: std::cell::OnceCell<Csr<T>>,
 }
 
 #[snippet("@SimpleGraph")]
@@ -16,9 +23,29 @@ impl<T: std::marker::Copy + std::cmp::PartialOrd> SimpleGraph<T> {
             size: n,
             edges: vec![vec![]; n],
             directed,
+            csr: std::cell::OnceCell::new(),
         }
     }
 
+    // 隣接リスト。辺の追加・削除後に最初に呼ばれた時のみO(n+m)で作り直す
+    // 辺の番号は、頂点0から出る辺、頂点1から出る辺、...の順に追加順で振られる
+    pub fn csr(&self) -> &Csr<T> {
+        self.csr.get_or_init(|| {
+            let edges: Vec<(usize, usize, T)> = self
+                .edges
+                .iter()
+                .enumerate()
+                .flat_map(|(from, v_edges)| v_edges.iter().map(move |&(to, cost)| (from, to, cost)))
+                .collect();
+            Csr::new(self.size, &edges)
+        })
+    }
+
+    // csrでid番目の辺(from, to, cost)。shortest_path_treeの辺番号から辺を得るのに使う O(log n)
+    pub fn edge(&self, id: usize) -> (usize, usize, T) {
+        self.csr().edge(id)
+    }
+
     // nodeに隣接する頂点を列挙
     pub fn adjacent_nodes(&self, node: usize) -> Vec<usize> {
         self.edges[node]
@@ -29,6 +56,7 @@ impl<T: std::marker::Copy + std::cmp::PartialOrd> SimpleGraph<T> {
 
     // 辺の追加 O(1)
     pub fn add_edge(&mut self, from: usize, to: usize, cost: T) {
+        self.csr.take();
         self.edges[from].push((to, cost));
 
         // 無向グラフなら、反対にも辺を張る
@@ -39,6 +67,7 @@ impl<T: std::marker::Copy + std::cmp::PartialOrd> SimpleGraph<T> {
 
     // 辺の削除 O(d(from)+d(to))
     pub fn remove_edge(&mut self, from: usize, to: usize) {
+        self.csr.take();
         let mut remove_index = std::usize::MAX;
         self.edges[from]
             .iter()
@@ -99,10 +128,12 @@ impl<T: std::marker::Copy + std::cmp::PartialOrd> SimpleGraph<T> {
             }
         });
 
+        let csr = self.csr();
+
         while isolated.len() != 0 {
             let next = isolated.pop_back().unwrap();
             result.push(next);
-            for &(to, _) in &self.edges[next] {
+            for &(to, _) in csr.adjacent(next) {
                 degs[to] -= 1;
                 if degs[to] == isolate_deg {
                     isolated.push_front(to);
@@ -115,113 +146,7 @@ impl<T: std::marker::Copy + std::cmp::PartialOrd> SimpleGraph<T> {
 
     /// return pair of (# of scc, scc id)
     fn scc_ids(&self) -> (usize, Vec<usize>) {
-        // Compressed sparse row?
-        pub struct Csr {
-            start: Vec<usize>,
-            elist: Vec<usize>,
-        }
-
-        impl Csr {
-            pub fn new(n: usize, edges: &Vec<Vec<usize>>) -> Self {
-                let mut e_count = 0;
-                edges.iter().for_each(|edges_f| {
-                    e_count += edges_f.len();
-                });
-
-                let mut csr = Csr {
-                    start: vec![0; n + 1],
-                    elist: vec![0; e_count],
-                };
-                for i in 0..edges.len() {
-                    let from = i;
-                    let edges_f = &edges[from];
-                    for _ in edges_f.iter() {
-                        csr.start[from + 1] += 1;
-                    }
-                }
-
-                for i in 1..=n {
-                    csr.start[i] += csr.start[i - 1];
-                }
-
-                let mut counter = csr.start.clone();
-                for i in 0..edges.len() {
-                    let edges_f = &edges[i];
-                    let from = i;
-                    for to in edges_f.iter() {
-                        csr.elist[counter[from]] = *to;
-                        counter[from] += 1;
-                    }
-                }
-
-                csr
-            }
-        }
-
-        struct _Env {
-            graph: Csr,
-            now_ord: usize,
-            group_num: usize,
-            visited: Vec<usize>,
-            low: Vec<usize>,
-            ord: Vec<Option<usize>>,
-            ids: Vec<usize>,
-        }
-
-        // pure_edges[from] = [to1,to2,to3]
-        let pure_edges: Vec<Vec<usize>> = self
-            .edges
-            .iter()
-            .map(|edges_f| edges_f.iter().map(|e| e.0).collect())
-            .collect();
-
-        let mut env = _Env {
-            graph: Csr::new(self.size, &pure_edges),
-            now_ord: 0,
-            group_num: 0,
-            visited: Vec::with_capacity(self.size),
-            low: vec![0; self.size],
-            ord: vec![None; self.size],
-            ids: vec![0; self.size],
-        };
-
-        fn dfs(v: usize, n: usize, env: &mut _Env) {
-            env.low[v] = env.now_ord;
-            env.ord[v] = Some(env.now_ord);
-            env.now_ord += 1;
-            env.visited.push(v);
-
-            for i in env.graph.start[v]..env.graph.start[v + 1] {
-                let to = env.graph.elist[i];
-                if let Some(x) = env.ord[to] {
-                    env.low[v] = std::cmp::min(env.low[v], x);
-                } else {
-                    dfs(to, n, env);
-                    env.low[v] = std::cmp::min(env.low[v], env.low[to]);
-                }
-            }
-            if env.low[v] == env.ord[v].unwrap() {
-                loop {
-                    let u = *env.visited.last().unwrap();
-                    env.visited.pop();
-                    env.ord[u] = Some(n);
-                    env.ids[u] = env.group_num;
-                    if u == v {
-                        break;
-                    }
-                }
-                env.group_num += 1;
-            }
-        }
-        for i in 0..self.size {
-            if env.ord[i].is_none() {
-                dfs(i, self.size, &mut env);
-            }
-        }
-        for x in env.ids.iter_mut() {
-            *x = env.group_num - 1 - *x;
-        }
-        (env.group_num, env.ids)
+        scc_ids(self.csr())
     }
 
     pub fn scc(&self) -> Vec<Vec<usize>> {
@@ -250,11 +175,14 @@ impl<T: std::marker::Copy + std::cmp::PartialOrd> SimpleGraph<T> {
         let mut visited = vec![std::usize::MAX; self.size];
         visited[start] = start;
 
+        let csr = self.csr();
+
         while !que.is_empty() {
             let v = que.pop_back().unwrap();
             visits.push(v);
 
-            let dead_end = self.edges[v]
+            let dead_end = csr
+                .adjacent(v)
                 .iter()
                 .all(|e| visited[e.0] != std::usize::MAX);
 
@@ -266,8 +194,7 @@ impl<T: std::marker::Copy + std::cmp::PartialOrd> SimpleGraph<T> {
                 continue;
             }
 
-            for i in 0..self.edges[v].len() {
-                let e = self.edges[v][i];
+            for &e in csr.adjacent(v) {
                 if visited[e.0] == std::usize::MAX {
                     visited[e.0] = v;
                     que.push_back(e.0);
@@ -295,6 +222,8 @@ impl<T: std::marker::Copy + std::cmp::PartialOrd> SimpleGraph<T> {
 
         let mut is_tree = true;
 
+        let csr = self.csr();
+
         while !que.is_empty() {
             let (now, before) = que.pop_back().unwrap();
             if visited[now] {
@@ -304,7 +233,7 @@ impl<T: std::marker::Copy + std::cmp::PartialOrd> SimpleGraph<T> {
                 visited[now] = true;
             }
 
-            self.edges[now].iter().for_each(|&(to, _)| {
+            csr.adjacent(now).iter().for_each(|&(to, _)| {
                 if to != before {
                     que.push_back((to, now));
                 }
@@ -331,13 +260,15 @@ impl<T: Copy + num::Unsigned + num::Bounded + num::Saturating + std::cmp::Ord> S
             std::collections::BinaryHeap::new();
         queue.push(std::cmp::Reverse((T::zero(), from)));
 
+        let csr = self.csr();
+
         while !queue.is_empty() {
             let (cost, to) = queue.pop().unwrap().0;
             if from_to_n[to] < cost {
                 continue;
             }
 
-            for &(to_to, to_cost) in &self.edges[to] {
+            for &(to_to, to_cost) in csr.adjacent(to) {
                 let new_cost = cost.saturating_add(to_cost);
                 if from_to_n[to_to] > new_cost {
                     from_to_n[to_to] = new_cost;
@@ -349,62 +280,290 @@ impl<T: Copy + num::Unsigned + num::Bounded + num::Saturating + std::cmp::Ord> S
         from_to_n
     }
 
+    // 辺のコストが0か1のときの1対nの最小距離 O(V+E)
+    // 返り値の形式はmin_distsと同じ
+    pub fn min_dists_01(&self, from: usize) -> Vec<T> {
+        let mut from_to_n = vec![T::max_value(); self.size];
+        let mut queue = std::collections::VecDeque::new();
+        queue.push_back((T::zero(), from));
+
+        let csr = self.csr();
+
+        while let Some((cost, to)) = queue.pop_front() {
+            if from_to_n[to] < cost {
+                continue;
+            }
+
+            for &(to_to, to_cost) in csr.adjacent(to) {
+                assert!(to_cost <= T::one());
+                let new_cost = cost.saturating_add(to_cost);
+                if from_to_n[to_to] > new_cost {
+                    from_to_n[to_to] = new_cost;
+                    if to_cost == T::zero() {
+                        queue.push_front((new_cost, to_to));
+                    } else {
+                        queue.push_back((new_cost, to_to));
+                    }
+                }
+            }
+        }
+
+        from_to_n
+    }
+
     pub fn all_min_dists(&self) -> Vec<Vec<T>> {
         (0..self.size).map(|node| self.min_dists(node)).collect()
     }
+
+    // min_distsと同じ最短距離に加えて、最短路で各頂点の1つ前の頂点と辺を求める
+    pub fn shortest_path_tree(&self, from: usize) -> ShortestPathTree<T> {
+        let mut from_to_n = vec![T::max_value(); self.size];
+        let mut prev_nodes = vec![None; self.size];
+        let mut prev_edges = vec![None; self.size];
+        let mut queue: std::collections::BinaryHeap<std::cmp::Reverse<(T, usize)>> =
+            std::collections::BinaryHeap::new();
+        queue.push(std::cmp::Reverse((T::zero(), from)));
+
+        let csr = self.csr();
+
+        while let Some(std::cmp::Reverse((cost, to))) = queue.pop() {
+            if from_to_n[to] < cost {
+                continue;
+            }
+
+            for (&(to_to, to_cost), &edge_id) in csr.adjacent(to).iter().zip(csr.edge_ids(to)) {
+                let new_cost = cost.saturating_add(to_cost);
+                if from_to_n[to_to] > new_cost {
+                    from_to_n[to_to] = new_cost;
+                    prev_nodes[to_to] = Some(to);
+                    prev_edges[to_to] = Some(edge_id);
+                    queue.push(std::cmp::Reverse((new_cost, to_to)));
+                }
+            }
+        }
+
+        ShortestPathTree::new(from, from_to_n, prev_nodes, prev_edges)
+    }
+}
+
+#[snippet("@SimpleGraph")]
+// 辺のコストが符号なし整数のグラフのためのメソッド
+impl<T: num::PrimInt + num::Unsigned> SimpleGraph<T> {
+    // min_distsと同じ結果をRadixHeapを用いたDijkstraで求める
+    pub fn min_dists_radix_heap(&self, from: usize) -> Vec<T> {
+        let mut from_to_n = vec![T::max_value(); self.size];
+        let mut queue = RadixHeap::new();
+        queue.push(T::zero(), from);
+
+        let csr = self.csr();
+
+        while let Some((cost, to)) = queue.pop() {
+            if from_to_n[to] < cost {
+                continue;
+            }
+
+            for &(to_to, to_cost) in csr.adjacent(to) {
+                let new_cost = cost.saturating_add(to_cost);
+                if from_to_n[to_to] > new_cost {
+                    from_to_n[to_to] = new_cost;
+                    queue.push(new_cost, to_to);
+                }
+            }
+        }
+
+        from_to_n
+    }
+
+    // Dial法で1対nの最小距離を求める。辺のコストはmax_cost以下 O(V*max_cost+E)
+    // 距離をmax_cost+1個のバケットで循環的に管理する。返り値の形式はmin_distsと同じ
+    pub fn min_dists_dial(&self, from: usize, max_cost: usize) -> Vec<T> {
+        let mut from_to_n = vec![T::max_value(); self.size];
+        let mut buckets = vec![vec![]; max_cost + 1];
+        buckets[0].push(from);
+        let mut rest = 1;
+
+        let csr = self.csr();
+
+        let mut dist = 0;
+        while rest > 0 {
+            while let Some(to) = buckets[dist % (max_cost + 1)].pop() {
+                rest -= 1;
+                let cost = T::from(dist).unwrap();
+                if from_to_n[to] < cost {
+                    continue;
+                }
+
+                for &(to_to, to_cost) in csr.adjacent(to) {
+                    assert!(to_cost.to_usize().unwrap() <= max_cost);
+                    let new_cost = cost + to_cost;
+                    if from_to_n[to_to] > new_cost {
+                        from_to_n[to_to] = new_cost;
+                        buckets[new_cost.to_usize().unwrap() % (max_cost + 1)].push(to_to);
+                        rest += 1;
+                    }
+                }
+            }
+            dist += 1;
+        }
+
+        from_to_n
+    }
 }
 
 // 負辺を含むグラフのためのメソッド
 #[snippet("@SimpleGraph")]
 impl<T: Copy + num::Signed + num::Bounded + std::ops::AddAssign + std::cmp::Ord> SimpleGraph<T> {
     // Bellman-Ford法で1対nの最小距離を求める。負閉路を検出した場合はNone
-    // このメソッドでは自己ループ辺を無視する。
+    // 負閉路の判定はbellman_fordと同じ
     pub fn min_dists_or_detect_negative_loop(&self, from: usize) -> Option<Vec<T>> {
         let mut from_to_n = vec![T::max_value(); self.size];
-        from_to_n[from] = T::zero();
-        for _ in 0..self.size {
-            self.edges.iter().enumerate().for_each(|(from_v, v_edges)| {
-                v_edges.iter().for_each(|&(to_v, cost)| {
-                    if from_to_n[from_v] != T::max_value() {
-                        from_to_n[to_v] = std::cmp::min(from_to_n[to_v], from_to_n[from_v] + cost);
+        for (to, dist) in self.bellman_ford(from).into_iter().enumerate() {
+            match dist {
+                Distance::Finite(dist) => from_to_n[to] = dist,
+                Distance::Unreachable => {}
+                Distance::NegativeInfinity => return None,
+            }
+        }
+
+        Some(from_to_n)
+    }
+
+    // Bellman-Ford法で1対nの最小距離を求める O(VE)
+    // fromから負閉路を経由して到達できる頂点はNegativeInfinity、到達できない頂点はUnreachableになる
+    pub fn bellman_ford(&self, from: usize) -> Vec<Distance<T>> {
+        let mut from_to_n: Vec<Option<T>> = vec![None; self.size];
+        from_to_n[from] = Some(T::zero());
+        for _ in 1..self.size {
+            let mut updated = false;
+            for (from, v_edges) in self.edges.iter().enumerate() {
+                for &(to, cost) in v_edges {
+                    if let Some(dist) = from_to_n[from] {
+                        if from_to_n[to].is_none() || Some(dist + cost) < from_to_n[to] {
+                            from_to_n[to] = Some(dist + cost);
+                            updated = true;
+                        }
                     }
-                });
-            });
+                }
+            }
+            if !updated {
+                break;
+            }
         }
 
-        let mut dist_total_before: T = T::zero();
+        // n-1周しても距離が更新される頂点と、そこから到達できる頂点は負閉路を経由できる
+        let mut negative = vec![false; self.size];
+        for _ in 0..self.size {
+            let mut updated = false;
+            for (from, v_edges) in self.edges.iter().enumerate() {
+                for &(to, cost) in v_edges {
+                    if negative[to] {
+                        continue;
+                    }
+                    if let Some(dist) = from_to_n[from] {
+                        if negative[from]
+                            || from_to_n[to].is_none()
+                            || Some(dist + cost) < from_to_n[to]
+                        {
+                            negative[to] = true;
+                            updated = true;
+                        }
+                    }
+                }
+            }
+            if !updated {
+                break;
+            }
+        }
 
-        from_to_n
-            .iter()
-            .filter(|&&dist| dist != T::max_value())
-            .copied()
-            .for_each(|x| {
-                dist_total_before += x;
-            });
+        (0..self.size)
+            .map(|v| match from_to_n[v] {
+                None => Distance::Unreachable,
+                Some(_) if negative[v] => Distance::NegativeInfinity,
+                Some(dist) => Distance::Finite(dist),
+            })
+            .collect()
+    }
 
-        // もう一周行う
-        self.edges.iter().enumerate().for_each(|(from_v, v_edges)| {
-            v_edges.iter().for_each(|&(to_v, cost)| {
-                if from_to_n[from_v] != T::max_value() {
-                    from_to_n[to_v] = std::cmp::min(from_to_n[to_v], from_to_n[from_v] + cost);
+    // グラフ中の負閉路を1つ求める。負閉路がなければNone O(VE)
+    // 返り値[v0, v1, ..., vk-1]は、辺v0->v1, ..., vk-1->v0からなる閉路
+    pub fn negative_cycle(&self) -> Option<Vec<usize>> {
+        // すべての頂点を始点として、距離0から始める
+        let mut dists = vec![T::zero(); self.size];
+        let mut prev_nodes = vec![usize::MAX; self.size];
+        let mut last_updated = None;
+        for _ in 0..self.size {
+            last_updated = None;
+            for (from, v_edges) in self.edges.iter().enumerate() {
+                for &(to, cost) in v_edges {
+                    if dists[from] + cost < dists[to] {
+                        dists[to] = dists[from] + cost;
+                        prev_nodes[to] = from;
+                        last_updated = Some(to);
+                    }
                 }
-            });
-        });
+            }
+            last_updated?;
+        }
 
-        let mut dist_total_after: T = T::zero();
+        // n周目でも更新された頂点から辺をn回さかのぼると、負閉路上の頂点に着く
+        let mut v = last_updated?;
+        for _ in 0..self.size {
+            v = prev_nodes[v];
+        }
 
-        from_to_n
-            .iter()
-            .filter(|&&dist| dist != T::max_value())
-            .copied()
-            .for_each(|x| {
-                dist_total_after += x;
-            });
+        let mut cycle = vec![v];
+        let mut u = prev_nodes[v];
+        while u != v {
+            cycle.push(u);
+            u = prev_nodes[u];
+        }
+        cycle.reverse();
+
+        Some(cycle)
+    }
+
+    // min_dists_or_detect_negative_loopと同じ最短距離に加えて、最短路で各頂点の1つ前の頂点と辺を求める
+    // fromから到達できる負閉路を検出した場合はNone
+    pub fn shortest_path_tree_or_detect_negative_loop(
+        &self,
+        from: usize,
+    ) -> Option<ShortestPathTree<T>> {
+        let mut from_to_n = vec![T::max_value(); self.size];
+        let mut prev_nodes = vec![None; self.size];
+        let mut prev_edges = vec![None; self.size];
+        from_to_n[from] = T::zero();
+
+        let csr = self.csr();
+
+        // 負閉路がなければn-1周以内に更新がなくなる
+        let mut updated = true;
+        for _ in 0..self.size {
+            if !updated {
+                break;
+            }
+            updated = false;
+            for from_v in 0..self.size {
+                for (&(to_v, cost), &edge_id) in
+                    csr.adjacent(from_v).iter().zip(csr.edge_ids(from_v))
+                {
+                    if from_to_n[from_v] != T::max_value()
+                        && from_to_n[from_v] + cost < from_to_n[to_v]
+                    {
+                        from_to_n[to_v] = from_to_n[from_v] + cost;
+                        prev_nodes[to_v] = Some(from_v);
+                        prev_edges[to_v] = Some(edge_id);
+                        updated = true;
+                    }
+                }
+            }
+        }
 
-        if dist_total_before != dist_total_after {
+        if updated {
             None
         } else {
-            Some(from_to_n)
+            Some(ShortestPathTree::new(
+                from, from_to_n, prev_nodes, prev_edges,
+            ))
         }
     }
 }
@@ -412,6 +571,7 @@ impl<T: Copy + num::Signed + num::Bounded + std::ops::AddAssign + std::cmp::Ord>
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::graph::distance::Distance;
     use proptest::prelude::*;
     use rand::prelude::*;
 
@@ -477,6 +637,27 @@ mod test {
         assert_eq!(graph.adjacent_nodes(4), vec![1]);
     }
 
+    #[test]
+    fn csr_rebuild_test() {
+        let mut graph = SimpleGraph::<usize>::new(3, true);
+        graph.add_edge(0, 1, 5);
+        assert_eq!(
+            graph.min_dists(0),
+            vec![std::usize::MAX, 5, std::usize::MAX]
+        );
+        assert_eq!(graph.csr().adjacent(0), &[(1, 5)]);
+
+        // 隣接リストを作った後に辺を追加しても反映される
+        graph.add_edge(1, 2, 1);
+        graph.add_edge(0, 2, 3);
+        assert_eq!(graph.adjacent_nodes(0), vec![1, 2]);
+        assert_eq!(graph.min_dists(0), vec![std::usize::MAX, 5, 3]);
+
+        graph.remove_edge(0, 2);
+        assert_eq!(graph.adjacent_nodes(0), vec![1]);
+        assert_eq!(graph.min_dists(0), vec![std::usize::MAX, 5, 6]);
+    }
+
     #[test]
     fn min_dists_test() {
         let mut graph = SimpleGraph::<usize>::new(4, true);
@@ -593,6 +774,208 @@ mod test {
         );
     }
 
+    #[test]
+    fn min_dists_radix_heap_test() {
+        let mut graph = SimpleGraph::<usize>::new(5, false);
+        graph.add_edge(0, 1, 90);
+        graph.add_edge(1, 2, 180);
+        graph.add_edge(0, 2, 150);
+        graph.add_edge(0, 3, 40);
+        graph.add_edge(3, 4, 9000);
+
+        assert_eq!(graph.min_dists_radix_heap(0), vec![80, 90, 150, 40, 9040]);
+        assert_eq!(
+            graph.min_dists_radix_heap(4),
+            vec![9040, 9130, 9190, 9000, 18000]
+        );
+
+        let mut graph = SimpleGraph::<u32>::new(3, true);
+        graph.add_edge(0, 1, u32::MAX - 1);
+        graph.add_edge(1, 2, 5);
+        assert_eq!(
+            graph.min_dists_radix_heap(0),
+            vec![u32::MAX, u32::MAX - 1, u32::MAX]
+        );
+    }
+
+    #[test]
+    fn min_dists_01_test() {
+        let mut graph = SimpleGraph::<usize>::new(6, true);
+        graph.add_edge(0, 1, 1);
+        graph.add_edge(0, 2, 0);
+        graph.add_edge(2, 3, 1);
+        graph.add_edge(1, 3, 0);
+        graph.add_edge(3, 4, 0);
+        graph.add_edge(4, 0, 1);
+
+        assert_eq!(graph.min_dists_01(0), vec![2, 1, 0, 1, 1, usize::MAX]);
+        assert_eq!(graph.min_dists_01(0), graph.min_dists(0));
+        assert_eq!(graph.min_dists_dial(0, 1), graph.min_dists(0));
+    }
+
+    #[test]
+    fn min_dists_dial_test() {
+        let mut graph = SimpleGraph::<u32>::new(5, false);
+        graph.add_edge(0, 1, 9);
+        graph.add_edge(1, 2, 8);
+        graph.add_edge(0, 2, 10);
+        graph.add_edge(0, 3, 4);
+        graph.add_edge(3, 4, 0);
+
+        assert_eq!(graph.min_dists_dial(0, 10), vec![8, 9, 10, 4, 4]);
+        assert_eq!(graph.min_dists_dial(2, 10), vec![10, 8, 16, 14, 14]);
+    }
+
+    proptest! {
+        #[test]
+        fn min_dists_01_random_test(graph_size :u8) {
+            let graph_size = graph_size as usize % 50 + 1;
+
+            let mut graph = SimpleGraph::<u64>::new(graph_size, true);
+            for _ in 0..graph_size * 3 {
+                let from: usize = random::<usize>() % graph_size;
+                let to: usize = random::<usize>() % graph_size;
+                graph.add_edge(from, to, random::<u64>() % 2);
+            }
+
+            for from in 0..graph_size {
+                prop_assert_eq!(graph.min_dists_01(from), graph.min_dists(from));
+            }
+        }
+
+        #[test]
+        fn min_dists_dial_random_test(graph_size :u8, max_cost :u8) {
+            let graph_size = graph_size as usize % 50 + 1;
+            let max_cost = max_cost as usize % 10;
+
+            let mut graph = SimpleGraph::<u64>::new(graph_size, true);
+            for _ in 0..graph_size * 3 {
+                let from: usize = random::<usize>() % graph_size;
+                let to: usize = random::<usize>() % graph_size;
+                graph.add_edge(from, to, random::<u64>() % (max_cost as u64 + 1));
+            }
+
+            for from in 0..graph_size {
+                prop_assert_eq!(graph.min_dists_dial(from, max_cost), graph.min_dists(from));
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn min_dists_radix_heap_random_test(graph_size :u8) {
+            let graph_size = graph_size as usize % 50 + 1;
+
+            let mut graph = SimpleGraph::<u64>::new(graph_size, true);
+            for _ in 0..graph_size * 3 {
+                let from: usize = random::<usize>() % graph_size;
+                let to: usize = random::<usize>() % graph_size;
+                graph.add_edge(from, to, random::<u64>() % 1000);
+            }
+
+            for from in 0..graph_size {
+                prop_assert_eq!(graph.min_dists_radix_heap(from), graph.min_dists(from));
+            }
+        }
+    }
+
+    #[test]
+    fn shortest_path_tree_test() {
+        let mut graph = SimpleGraph::<usize>::new(5, true);
+        graph.add_edge(0, 1, 90);
+        graph.add_edge(1, 2, 180);
+        graph.add_edge(0, 2, 150);
+        graph.add_edge(0, 3, 40);
+        graph.add_edge(3, 4, 9000);
+
+        let tree = graph.shortest_path_tree(0);
+        assert_eq!(tree.dists(), graph.min_dists(0).as_slice());
+        assert_eq!(tree.path_to(4), Some(vec![0, 3, 4]));
+        assert_eq!(tree.edge_path_to(4), Some(vec![2, 4]));
+        assert_eq!(graph.edge(2), (0, 3, 40));
+        assert_eq!(graph.edge(4), (3, 4, 9000));
+        assert_eq!(tree.path_to(2), Some(vec![0, 2]));
+        assert_eq!(tree.edge_path_to(2), Some(vec![1]));
+        assert_eq!(tree.prev_node(1), Some(0));
+        assert_eq!(tree.path_to(0), Some(vec![0]));
+        assert_eq!(graph.shortest_path_tree(2).path_to(0), None);
+
+        // 始点に戻る閉路
+        let mut graph = SimpleGraph::<usize>::new(4, true);
+        graph.add_edge(0, 1, 100);
+        graph.add_edge(1, 2, 100);
+        graph.add_edge(2, 3, 150);
+        graph.add_edge(3, 0, 3);
+        let tree = graph.shortest_path_tree(0);
+        assert_eq!(tree.dists()[0], 353);
+        assert_eq!(tree.path_to(0), Some(vec![0, 1, 2, 3, 0]));
+    }
+
+    #[test]
+    fn shortest_path_tree_i64_test() {
+        let mut graph = SimpleGraph::<i64>::new(5, true);
+        graph.add_edge(0, 1, -50);
+        graph.add_edge(1, 0, 70);
+        graph.add_edge(1, 2, 180);
+        graph.add_edge(0, 2, 150);
+        graph.add_edge(0, 3, 40);
+        graph.add_edge(3, 4, 9000);
+
+        let tree = graph.shortest_path_tree_or_detect_negative_loop(0).unwrap();
+        assert_eq!(tree.dists(), &[0, -50, 130, 40, 9040]);
+        assert_eq!(tree.path_to(2), Some(vec![0, 1, 2]));
+        assert_eq!(tree.edge_path_to(2), Some(vec![0, 4]));
+        assert_eq!(tree.path_to(0), Some(vec![0]));
+
+        let mut graph = SimpleGraph::<i64>::new(3, true);
+        graph.add_edge(0, 1, -70);
+        graph.add_edge(1, 0, 69);
+        graph.add_edge(0, 2, 200);
+        assert!(graph
+            .shortest_path_tree_or_detect_negative_loop(0)
+            .is_none());
+        assert!(graph
+            .shortest_path_tree_or_detect_negative_loop(2)
+            .is_some());
+    }
+
+    proptest! {
+        #[test]
+        fn shortest_path_tree_random_test(graph_size :u8) {
+            let graph_size = graph_size as usize % 30 + 1;
+
+            let mut graph = SimpleGraph::<u64>::new(graph_size, random());
+            for _ in 0..graph_size * 2 {
+                let from: usize = random::<usize>() % graph_size;
+                let to: usize = random::<usize>() % graph_size;
+                graph.add_edge(from, to, random::<u64>() % 100);
+            }
+            for from in 0..graph_size {
+                let tree = graph.shortest_path_tree(from);
+                let dists = graph.min_dists(from);
+                prop_assert_eq!(tree.dists(), dists.as_slice());
+                for to in 0..graph_size {
+                    match tree.edge_path_to(to) {
+                        None => prop_assert_eq!(dists[to], u64::MAX),
+                        Some(edge_path) => {
+                            let path = tree.path_to(to).unwrap();
+                            prop_assert_eq!(path.len(), edge_path.len() + 1);
+                            let mut cost = 0;
+                            for (i, &edge_id) in edge_path.iter().enumerate() {
+                                let (edge_from, edge_to, edge_cost) = graph.edge(edge_id);
+                                prop_assert_eq!((edge_from, edge_to), (path[i], path[i + 1]));
+                                cost += edge_cost;
+                            }
+                            if !edge_path.is_empty() {
+                                prop_assert_eq!(cost, dists[to]);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
     #[test]
     fn min_dists_i64_test() {
         let mut graph = SimpleGraph::<i64>::new(5, true);
@@ -634,6 +1017,112 @@ mod test {
         assert_eq!(graph.min_dists_or_detect_negative_loop(0), None);
     }
 
+    #[test]
+    fn bellman_ford_test() {
+        let mut graph = SimpleGraph::<i64>::new(6, true);
+        graph.add_edge(0, 1, 3);
+        graph.add_edge(1, 2, -2);
+        graph.add_edge(2, 3, 4);
+        // 3 -> 4 -> 3 が負閉路になる
+        graph.add_edge(3, 4, -5);
+        graph.add_edge(4, 3, 1);
+        graph.add_edge(5, 0, 1);
+
+        assert_eq!(
+            graph.bellman_ford(0),
+            vec![
+                Distance::Finite(0),
+                Distance::Finite(3),
+                Distance::Finite(1),
+                Distance::NegativeInfinity,
+                Distance::NegativeInfinity,
+                Distance::Unreachable,
+            ]
+        );
+        assert_eq!(graph.min_dists_or_detect_negative_loop(0), None);
+        // 負閉路があっても、fromから到達できなければ関係ない
+        assert_eq!(
+            graph.bellman_ford(2)[..3],
+            [
+                Distance::Unreachable,
+                Distance::Unreachable,
+                Distance::Finite(0)
+            ]
+        );
+        // 閉路の始点はどこになってもよい
+        let cycle = graph.negative_cycle().unwrap();
+        assert!(cycle == vec![3, 4] || cycle == vec![4, 3]);
+
+        let mut graph = SimpleGraph::<i64>::new(3, true);
+        graph.add_edge(0, 1, 1);
+        graph.add_edge(1, 2, -1);
+        graph.add_edge(2, 0, 0);
+        assert_eq!(graph.negative_cycle(), None);
+        graph.add_edge(2, 2, -1);
+        assert_eq!(graph.negative_cycle(), Some(vec![2]));
+        assert_eq!(graph.min_dists_or_detect_negative_loop(0), None);
+    }
+
+    #[test]
+    fn bellman_ford_overflow_test() {
+        // 距離の総和はオーバーフローするが、各頂点の距離は範囲内に収まる
+        let mut graph = SimpleGraph::<i64>::new(3, true);
+        graph.add_edge(0, 1, i64::MAX / 2);
+        graph.add_edge(0, 2, i64::MAX / 2);
+        assert_eq!(
+            graph.min_dists_or_detect_negative_loop(0),
+            Some(vec![0, i64::MAX / 2, i64::MAX / 2])
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn bellman_ford_random_test(graph_size :u8) {
+            let graph_size = graph_size as usize % 8 + 1;
+
+            let mut graph = SimpleGraph::<i64>::new(graph_size, true);
+            for _ in 0..graph_size * 2 {
+                let from: usize = random::<usize>() % graph_size;
+                let to: usize = random::<usize>() % graph_size;
+                graph.add_edge(from, to, random::<i64>() % 20 - 3);
+            }
+            let edges: Vec<(usize, usize, i64)> = graph
+                .edges
+                .iter()
+                .enumerate()
+                .flat_map(|(from, v_edges)| v_edges.iter().map(move |&(to, cost)| (from, to, cost)))
+                .collect();
+            let warshall_floyd = crate::graph::warshall_floyd::WarshallFloyd::from_edges(graph_size, &edges);
+
+            for from in 0..graph_size {
+                let dists = graph.bellman_ford(from);
+                for to in 0..graph_size {
+                    prop_assert_eq!(dists[to], warshall_floyd.dist(from, to));
+                }
+            }
+
+            match graph.negative_cycle() {
+                Some(cycle) => {
+                    prop_assert!(warshall_floyd.has_negative_cycle());
+                    let mut total = 0;
+                    for i in 0..cycle.len() {
+                        let from = cycle[i];
+                        let to = cycle[(i + 1) % cycle.len()];
+                        let cost = graph.edges[from]
+                .iter()
+                .filter(|e| e.0 == to)
+                .map(|e| e.1)
+                .min()
+                .unwrap();
+                        total += cost;
+                    }
+                    prop_assert!(total < 0);
+                }
+                None => prop_assert!(!warshall_floyd.has_negative_cycle()),
+            }
+        }
+    }
+
     #[test]
     fn min_dists_undirected_test() {
         // 上のテストを無向グラフにしたもの
@@ -711,6 +1200,20 @@ mod test {
         assert_eq!(scc, vec![vec![4], vec![3], vec![2], vec![1], vec![0]]);
     }
 
+    #[test]
+    fn scc_long_path_test() {
+        // 再帰で実装するとスタックが溢れる長さのパス
+        let n = 1_000_000;
+        let mut graph = SimpleGraph::<usize>::new(n, true);
+        for v in 1..n {
+            graph.add_edge(v - 1, v, 1);
+        }
+        assert_eq!(graph.scc().len(), n);
+
+        graph.add_edge(n - 1, 0, 1);
+        assert_eq!(graph.scc().len(), 1);
+    }
+
     #[test]
     fn euler_tour_test() {
         let mut graph = SimpleGraph::<usize>::new(7, false);
@@ -0,0 +1,324 @@
+use crate::graph::csr::Csr;
+use cargo_snippet::snippet;
+
+// 根付き木。ダブリングでLCAやk個上の祖先を求める
+// 構築はO(n log n)、各クエリはO(log n)
+#[snippet("@Tree")]
+#[snippet("@Graph")]
+#[snippet("@HeavyLightDecomposition")]
+#[snippet("@CentroidDecomposition")]
+pub struct Tree<T> {
+    root: usize,
+    parents: Vec<Option<usize>>,
+    // 根から辺をたどった回数
+    depths: Vec<usize>,
+    // 根からの辺のコストの和
+    weighted_depths: Vec<T>,
+    subtree_sizes: Vec<usize>,
+    // 根からのBFSの訪問順。親は必ず子より前に来る
+    order: Vec<usize>,
+    // ancestors[k][v]: vの2^k個上の祖先。根を超える場合は根
+    ancestors: Vec<Vec<usize>>,
+}
+
+#[snippet("@Tree")]
+#[snippet("@Graph")]
+#[snippet("@HeavyLightDecomposition")]
+#[snippet("@CentroidDecomposition")]
+impl<T: Copy + num::Num> Tree<T> {
+    // edges: 無向辺(u, v, cost)の列。n-1本の辺で連結になっていること O(n log n)
+    pub fn new(n: usize, edges: &[(usize, usize, T)], root: usize) -> Tree<T> {
+        assert_eq!(edges.len() + 1, n);
+        let directed_edges: Vec<(usize, usize, T)> = edges
+            .iter()
+            .flat_map(|&(u, v, cost)| vec![(u, v, cost), (v, u, cost)])
+            .collect();
+
+        Tree::from_csr(&Csr::new(n, &directed_edges), root)
+    }
+
+    // 隣接リストから作る。無向辺は両方向の辺を含むこと O(n log n)
+    pub fn from_csr(csr: &Csr<T>, root: usize) -> Tree<T> {
+        let n = csr.size();
+        let mut parents = vec![None; n];
+        let mut depths = vec![0; n];
+        let mut weighted_depths = vec![T::zero(); n];
+        let mut visited = vec![false; n];
+        let mut order = Vec::with_capacity(n);
+        visited[root] = true;
+        order.push(root);
+        let mut index = 0;
+        while index < order.len() {
+            let v = order[index];
+            index += 1;
+            for &(to, cost) in csr.adjacent(v) {
+                if visited[to] {
+                    continue;
+                }
+                visited[to] = true;
+                parents[to] = Some(v);
+                depths[to] = depths[v] + 1;
+                weighted_depths[to] = weighted_depths[v] + cost;
+                order.push(to);
+            }
+        }
+        assert_eq!(order.len(), n, "the graph is not connected");
+
+        let mut subtree_sizes = vec![1; n];
+        for &v in order.iter().rev() {
+            if let Some(parent) = parents[v] {
+                subtree_sizes[parent] += subtree_sizes[v];
+            }
+        }
+
+        let mut log = 1;
+        while (1 << log) < n {
+            log += 1;
+        }
+        let mut ancestors = vec![(0..n)
+            .map(|v| parents[v].unwrap_or(root))
+            .collect::<Vec<_>>()];
+        for k in 1..log {
+            let next = (0..n)
+                .map(|v| ancestors[k - 1][ancestors[k - 1][v]])
+                .collect();
+            ancestors.push(next);
+        }
+
+        Tree {
+            root,
+            parents,
+            depths,
+            weighted_depths,
+            subtree_sizes,
+            order,
+            ancestors,
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.parents.len()
+    }
+
+    pub fn root(&self) -> usize {
+        self.root
+    }
+
+    pub fn parent(&self, v: usize) -> Option<usize> {
+        self.parents[v]
+    }
+
+    pub fn depth(&self, v: usize) -> usize {
+        self.depths[v]
+    }
+
+    pub fn weighted_depth(&self, v: usize) -> T {
+        self.weighted_depths[v]
+    }
+
+    pub fn subtree_size(&self, v: usize) -> usize {
+        self.subtree_sizes[v]
+    }
+
+    // 根からのBFSの訪問順
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
+
+    // vのk個上の祖先。根を超える場合はNone
+    pub fn kth_ancestor(&self, mut v: usize, k: usize) -> Option<usize> {
+        if k > self.depths[v] {
+            return None;
+        }
+
+        for (i, ancestors) in self.ancestors.iter().enumerate() {
+            if (k >> i) & 1 == 1 {
+                v = ancestors[v];
+            }
+        }
+
+        Some(v)
+    }
+
+    pub fn lca(&self, u: usize, v: usize) -> usize {
+        let (mut u, mut v) = if self.depths[u] < self.depths[v] {
+            (v, u)
+        } else {
+            (u, v)
+        };
+        u = self
+            .kth_ancestor(u, self.depths[u] - self.depths[v])
+            .unwrap();
+        if u == v {
+            return u;
+        }
+
+        for ancestors in self.ancestors.iter().rev() {
+            if ancestors[u] != ancestors[v] {
+                u = ancestors[u];
+                v = ancestors[v];
+            }
+        }
+
+        self.parents[u].unwrap()
+    }
+
+    // uとvの間の辺の数
+    pub fn dist(&self, u: usize, v: usize) -> usize {
+        self.depths[u] + self.depths[v] - 2 * self.depths[self.lca(u, v)]
+    }
+
+    // uとvの間の辺のコストの和
+    pub fn weighted_dist(&self, u: usize, v: usize) -> T {
+        let lca = self.lca(u, v);
+        self.weighted_depths[u] + self.weighted_depths[v]
+            - self.weighted_depths[lca]
+            - self.weighted_depths[lca]
+    }
+
+    // uからvへのパスでk番目(0-indexed)の頂点。パスの長さを超える場合はNone
+    pub fn jump(&self, u: usize, v: usize, k: usize) -> Option<usize> {
+        let lca = self.lca(u, v);
+        let up = self.depths[u] - self.depths[lca];
+        let down = self.depths[v] - self.depths[lca];
+        if k <= up {
+            self.kth_ancestor(u, k)
+        } else if k <= up + down {
+            self.kth_ancestor(v, up + down - k)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::graph::Graph;
+    use proptest::prelude::*;
+    use rand::prelude::*;
+
+    #[test]
+    fn tree_test() {
+        //       0
+        //     /   \
+        //    1     2
+        //   / \     \
+        //  3   4     5
+        //            |
+        //            6
+        let edges = vec![
+            (0, 1, 2i64),
+            (0, 2, 1),
+            (1, 3, 5),
+            (4, 1, 3),
+            (2, 5, -1),
+            (5, 6, 4),
+        ];
+        let tree = Tree::new(7, &edges, 0);
+
+        assert_eq!(tree.root(), 0);
+        assert_eq!(tree.parent(0), None);
+        assert_eq!(tree.parent(4), Some(1));
+        assert_eq!(tree.depth(6), 3);
+        assert_eq!(tree.weighted_depth(6), 4);
+        assert_eq!(tree.subtree_size(1), 3);
+        assert_eq!(tree.subtree_size(2), 3);
+        assert_eq!(tree.subtree_size(0), 7);
+        assert_eq!(tree.lca(3, 4), 1);
+        assert_eq!(tree.lca(3, 6), 0);
+        assert_eq!(tree.lca(5, 6), 5);
+        assert_eq!(tree.dist(3, 6), 5);
+        assert_eq!(tree.weighted_dist(3, 6), 11);
+        assert_eq!(tree.kth_ancestor(6, 2), Some(2));
+        assert_eq!(tree.kth_ancestor(6, 3), Some(0));
+        assert_eq!(tree.kth_ancestor(6, 4), None);
+        assert_eq!(tree.jump(3, 6, 0), Some(3));
+        assert_eq!(tree.jump(3, 6, 2), Some(0));
+        assert_eq!(tree.jump(3, 6, 4), Some(5));
+        assert_eq!(tree.jump(3, 6, 5), Some(6));
+        assert_eq!(tree.jump(3, 6, 6), None);
+
+        // 根を変えてGraphから作る
+        let mut graph = Graph::new(7, false);
+        for &(u, v, cost) in &edges {
+            graph.add_edge(u, v, cost);
+        }
+        let tree = graph.tree(6);
+        assert_eq!(tree.parent(5), Some(6));
+        assert_eq!(tree.depth(3), 5);
+        assert_eq!(tree.lca(3, 4), 1);
+        assert_eq!(tree.lca(3, 2), 2);
+        assert_eq!(tree.weighted_dist(3, 6), 11);
+
+        let tree = Tree::<i64>::new(1, &[], 0);
+        assert_eq!(tree.lca(0, 0), 0);
+        assert_eq!(tree.jump(0, 0, 0), Some(0));
+    }
+
+    proptest! {
+        #[test]
+        fn tree_random_test(n :u8) {
+            let n = n as usize % 50 + 1;
+            let edges: Vec<(usize, usize, i64)> = (1..n)
+                .map(|v| (random::<usize>() % v, v, random::<i64>() % 100))
+                .collect();
+            let root = random::<usize>() % n;
+            let tree = Tree::new(n, &edges, root);
+
+            // 根から親をたどったパス
+            let path_to_root = |mut v: usize| {
+                let mut path = vec![v];
+                while let Some(parent) = tree.parent(v) {
+                    path.push(parent);
+                    v = parent;
+                }
+                path
+            };
+
+            for _ in 0..10 {
+                let u = random::<usize>() % n;
+                let v = random::<usize>() % n;
+                let u_path = path_to_root(u);
+                let v_path = path_to_root(v);
+                prop_assert_eq!(tree.depth(u), u_path.len() - 1);
+                prop_assert_eq!(*u_path.last().unwrap(), root);
+
+                let lca = *u_path.iter().find(|w| v_path.contains(w)).unwrap();
+                prop_assert_eq!(tree.lca(u, v), lca);
+
+                // u -> lca -> vの頂点列
+                let mut path: Vec<usize> = u_path.iter().take_while(|&&w| w != lca).copied().collect();
+                path.push(lca);
+                let rest: Vec<usize> = v_path.iter().take_while(|&&w| w != lca).copied().collect();
+                path.extend(rest.iter().rev());
+                prop_assert_eq!(tree.dist(u, v), path.len() - 1);
+
+                let mut weighted_dist = 0;
+                for i in 0..path.len() - 1 {
+                    weighted_dist += edges
+                        .iter()
+                        .find(|e| (e.0, e.1) == (path[i], path[i + 1]) || (e.1, e.0) == (path[i], path[i + 1]))
+                        .unwrap()
+                        .2;
+                }
+                prop_assert_eq!(tree.weighted_dist(u, v), weighted_dist);
+
+                for k in 0..=path.len() {
+                    prop_assert_eq!(tree.jump(u, v, k), path.get(k).copied());
+                    prop_assert_eq!(tree.kth_ancestor(u, k), u_path.get(k).copied());
+                }
+            }
+
+            let mut sizes = vec![0; n];
+            for v in 0..n {
+                for w in path_to_root(v) {
+                    sizes[w] += 1;
+                }
+            }
+            for v in 0..n {
+                prop_assert_eq!(tree.subtree_size(v), sizes[v]);
+            }
+        }
+    }
+}
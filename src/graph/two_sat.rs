@@ -0,0 +1,183 @@
+use crate::graph::csr::Csr;
+use crate::graph::scc::scc_ids;
+use cargo_snippet::snippet;
+
+// 2-SAT。(x_i == f) ∨ (x_j == g) の形の節の論理積を満たす割り当てを求める
+// 頂点2iを x_i == true、頂点2i+1を x_i == false とする含意グラフのSCCで解く O(n+m)
+#[snippet("@TwoSat")]
+pub struct TwoSat {
+    size: usize,
+    // at_most_oneで追加した補助変数を含む変数の数
+    variables: usize,
+    // 節に含まれる2つのリテラル(頂点番号)
+    clauses: Vec<(usize, usize)>,
+    answer: Vec<bool>,
+}
+
+#[snippet("@TwoSat")]
+impl TwoSat {
+    pub fn new(n: usize) -> TwoSat {
+        TwoSat {
+            size: n,
+            variables: n,
+            clauses: vec![],
+            answer: vec![false; n],
+        }
+    }
+
+    fn literal(i: usize, f: bool) -> usize {
+        2 * i + if f { 0 } else { 1 }
+    }
+
+    // (x_i == f) ∨ (x_j == g) を追加する
+    pub fn add_clause(&mut self, i: usize, f: bool, j: usize, g: bool) {
+        assert!(i < self.variables && j < self.variables);
+        self.clauses
+            .push((TwoSat::literal(i, f), TwoSat::literal(j, g)));
+    }
+
+    // (x_i == f) ならば (x_j == g)
+    pub fn implies(&mut self, i: usize, f: bool, j: usize, g: bool) {
+        self.add_clause(i, !f, j, g);
+    }
+
+    // x_i == f に固定する
+    pub fn set(&mut self, i: usize, f: bool) {
+        self.add_clause(i, f, i, f);
+    }
+
+    // literalsの(x_i == f)のうち、真になるのは高々1つ
+    // 補助変数を使い、O(k)個の節で表す
+    pub fn at_most_one(&mut self, literals: &[(usize, bool)]) {
+        if literals.len() <= 1 {
+            return;
+        }
+
+        // 補助変数s_t: literals[0..=t]のどれかが真
+        let start = self.variables;
+        self.variables += literals.len();
+        for (t, &(i, f)) in literals.iter().enumerate() {
+            self.implies(i, f, start + t, true);
+            if t > 0 {
+                self.implies(start + t - 1, true, start + t, true);
+                self.implies(start + t - 1, true, i, !f);
+            }
+        }
+    }
+
+    // 全ての節を満たす割り当てがあるかどうか。あればanswerで取得できる
+    pub fn satisfiable(&mut self) -> bool {
+        // ¬a -> b, ¬b -> a
+        let edges: Vec<(usize, usize, ())> = self
+            .clauses
+            .iter()
+            .flat_map(|&(a, b)| vec![(a ^ 1, b, ()), (b ^ 1, a, ())])
+            .collect();
+
+        // SCCの番号はトポロジカル順なので、後ろにある方を真にする
+        let (_, ids) = scc_ids(&Csr::new(2 * self.variables, &edges));
+        let mut answer = Vec::with_capacity(self.variables);
+        for i in 0..self.variables {
+            if ids[2 * i] == ids[2 * i + 1] {
+                return false;
+            }
+            answer.push(ids[2 * i] > ids[2 * i + 1]);
+        }
+        answer.truncate(self.size);
+        self.answer = answer;
+
+        true
+    }
+
+    // 最後にsatisfiableがtrueを返したときの割り当て
+    pub fn answer(&self) -> &[bool] {
+        &self.answer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use rand::prelude::*;
+
+    #[test]
+    fn two_sat_test() {
+        // (x0 ∨ x1) ∧ (¬x0 ∨ x1) ∧ (¬x1 ∨ ¬x2)
+        let mut two_sat = TwoSat::new(3);
+        two_sat.add_clause(0, true, 1, true);
+        two_sat.add_clause(0, false, 1, true);
+        two_sat.add_clause(1, false, 2, false);
+        assert!(two_sat.satisfiable());
+        assert_eq!(two_sat.answer()[1..], [true, false]);
+
+        two_sat.set(2, true);
+        assert!(!two_sat.satisfiable());
+
+        let mut two_sat = TwoSat::new(4);
+        two_sat.at_most_one(&[(0, true), (1, false), (2, true), (3, true)]);
+        two_sat.set(0, true);
+        assert!(two_sat.satisfiable());
+        assert_eq!(two_sat.answer(), &[true, true, false, false]);
+        two_sat.implies(0, true, 3, true);
+        assert!(!two_sat.satisfiable());
+    }
+
+    #[test]
+    fn two_sat_long_chain_test() {
+        // x0 -> x1 -> ... -> x_{n-1} の長い含意の鎖
+        let n = 500_000;
+        let mut two_sat = TwoSat::new(n);
+        for i in 0..n - 1 {
+            two_sat.implies(i, true, i + 1, true);
+        }
+        two_sat.set(0, true);
+        assert!(two_sat.satisfiable());
+        assert!(two_sat.answer().iter().all(|&x| x));
+
+        two_sat.set(n - 1, false);
+        assert!(!two_sat.satisfiable());
+
+        let mut two_sat = TwoSat::new(n);
+        let literals: Vec<(usize, bool)> = (0..n).map(|i| (i, true)).collect();
+        two_sat.at_most_one(&literals);
+        two_sat.set(n / 2, true);
+        assert!(two_sat.satisfiable());
+        assert_eq!(two_sat.answer().iter().filter(|&&x| x).count(), 1);
+    }
+
+    proptest! {
+        #[test]
+        fn two_sat_random_test(n :u8, m :u8) {
+            let n = n as usize % 6 + 1;
+            let m = m as usize % 12;
+            let random_literal = || (random::<usize>() % n, random::<bool>());
+            let clauses: Vec<((usize, bool), (usize, bool))> = (0..m)
+                .map(|_| (random_literal(), random_literal()))
+                .collect();
+            let at_most_one: Vec<(usize, bool)> = (0..random::<usize>() % 4)
+                .map(|_| random_literal())
+                .collect();
+
+            let mut two_sat = TwoSat::new(n);
+            for &((i, f), (j, g)) in &clauses {
+                two_sat.add_clause(i, f, j, g);
+            }
+            two_sat.at_most_one(&at_most_one);
+
+            let check = |answer: &[bool]| {
+                clauses.iter().all(|&((i, f), (j, g))| answer[i] == f || answer[j] == g)
+                    && at_most_one.iter().filter(|&&(i, f)| answer[i] == f).count() <= 1
+            };
+            let expected = (0..1 << n).any(|mask: usize| {
+                let answer: Vec<bool> = (0..n).map(|i| (mask >> i) & 1 == 1).collect();
+                check(&answer)
+            });
+            prop_assert_eq!(two_sat.satisfiable(), expected);
+            if expected {
+                prop_assert_eq!(two_sat.answer().len(), n);
+                prop_assert!(check(two_sat.answer()));
+            }
+        }
+    }
+}
@@ -0,0 +1,271 @@
+use crate::graph::distance::Distance;
+use cargo_snippet::snippet;
+
+// 負辺を含む有向グラフの全点対最短距離。頂点は0-indexed
+// 構築はO(V^3)、辺の追加はO(V^2)。無向辺は両方向の辺を追加する
+// dists[i][i]は0から始まる(自己ループ辺のコストにはならない)。負の自己ループ辺は負閉路として扱う
+#[snippet("@WarshallFloyd")]
+#[snippet("@Graph")]
+pub struct WarshallFloyd<T> {
+    size: usize,
+    // dists[i][j]: iからjへの歩道のコストの最小値の上界。到達できなければT::max_value()
+    dists: Vec<Vec<T>>,
+    // nexts[i][j]: iからjへの最短路でiの次の頂点
+    nexts: Vec<Vec<usize>>,
+}
+
+#[snippet("@WarshallFloyd")]
+#[snippet("@Graph")]
+impl<T: Copy + num::Signed + num::Bounded + num::Saturating + std::cmp::Ord> WarshallFloyd<T> {
+    // 辺のないn頂点のグラフ
+    pub fn new(n: usize) -> WarshallFloyd<T> {
+        let mut dists = vec![vec![T::max_value(); n]; n];
+        let mut nexts = vec![vec![usize::MAX; n]; n];
+        for i in 0..n {
+            dists[i][i] = T::zero();
+            nexts[i][i] = i;
+        }
+
+        WarshallFloyd {
+            size: n,
+            dists,
+            nexts,
+        }
+    }
+
+    // edges: (from, to, cost)の列 O(V^3)
+    pub fn from_edges(n: usize, edges: &[(usize, usize, T)]) -> WarshallFloyd<T> {
+        let mut warshall_floyd = WarshallFloyd::new(n);
+        for &(from, to, cost) in edges {
+            if cost < warshall_floyd.dists[from][to] {
+                warshall_floyd.dists[from][to] = cost;
+                warshall_floyd.nexts[from][to] = to;
+            }
+        }
+
+        for k in 0..n {
+            for i in 0..n {
+                if warshall_floyd.dists[i][k] == T::max_value() {
+                    continue;
+                }
+                for j in 0..n {
+                    if warshall_floyd.dists[k][j] == T::max_value() {
+                        continue;
+                    }
+                    // 負閉路があると値がいくらでも小さくなるので、オーバーフローしないようにする
+                    let new_dist =
+                        warshall_floyd.dists[i][k].saturating_add(warshall_floyd.dists[k][j]);
+                    if new_dist < warshall_floyd.dists[i][j] {
+                        warshall_floyd.dists[i][j] = new_dist;
+                        warshall_floyd.nexts[i][j] = warshall_floyd.nexts[i][k];
+                    }
+                }
+            }
+        }
+
+        warshall_floyd
+    }
+
+    // from -> toの辺を追加する O(V^2)
+    pub fn add_edge(&mut self, from: usize, to: usize, cost: T) {
+        let to_dists = self.dists[to].clone();
+        for i in 0..self.size {
+            let dist_to_from = self.dists[i][from];
+            if dist_to_from == T::max_value() {
+                continue;
+            }
+            let next = if i == from { to } else { self.nexts[i][from] };
+            let dist_to_to = dist_to_from.saturating_add(cost);
+            for (j, &to_dist) in to_dists.iter().enumerate() {
+                if to_dist == T::max_value() {
+                    continue;
+                }
+                let new_dist = dist_to_to.saturating_add(to_dist);
+                if new_dist < self.dists[i][j] {
+                    self.dists[i][j] = new_dist;
+                    self.nexts[i][j] = next;
+                }
+            }
+        }
+    }
+
+    // from -> toの距離 O(V)
+    pub fn dist(&self, from: usize, to: usize) -> Distance<T> {
+        if self.dists[from][to] == T::max_value() {
+            return Distance::Unreachable;
+        }
+
+        // 負閉路上の頂点を経由できるならいくらでも小さくなる
+        let through_negative_cycle = (0..self.size).any(|k| {
+            self.dists[k][k] < T::zero()
+                && self.dists[from][k] != T::max_value()
+                && self.dists[k][to] != T::max_value()
+        });
+        if through_negative_cycle {
+            Distance::NegativeInfinity
+        } else {
+            Distance::Finite(self.dists[from][to])
+        }
+    }
+
+    // 全点対の距離。負閉路がなければO(V^2)
+    pub fn all_dists(&self) -> Vec<Vec<Distance<T>>> {
+        let negative_nodes: Vec<usize> = (0..self.size)
+            .filter(|&k| self.dists[k][k] < T::zero())
+            .collect();
+
+        (0..self.size)
+            .map(|from| {
+                (0..self.size)
+                    .map(|to| {
+                        if self.dists[from][to] == T::max_value() {
+                            Distance::Unreachable
+                        } else if negative_nodes.iter().any(|&k| {
+                            self.dists[from][k] != T::max_value()
+                                && self.dists[k][to] != T::max_value()
+                        }) {
+                            Distance::NegativeInfinity
+                        } else {
+                            Distance::Finite(self.dists[from][to])
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    // 負閉路が存在するかどうか
+    pub fn has_negative_cycle(&self) -> bool {
+        (0..self.size).any(|k| self.dists[k][k] < T::zero())
+    }
+
+    // fromからtoへの最短路の頂点列(両端を含む)。距離が有限でなければNone O(V^2)
+    pub fn path(&self, from: usize, to: usize) -> Option<Vec<usize>> {
+        self.dist(from, to).finite()?;
+
+        let mut path = vec![from];
+        let mut now = from;
+        while now != to {
+            now = self.nexts[now][to];
+            path.push(now);
+        }
+
+        Some(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use rand::prelude::*;
+
+    #[test]
+    fn warshall_floyd_test() {
+        let edges = vec![(0, 1, 3i64), (1, 2, -2), (0, 2, 5), (2, 3, 4), (3, 1, 1)];
+        let warshall_floyd = WarshallFloyd::from_edges(5, &edges);
+
+        assert_eq!(warshall_floyd.dist(0, 2), Distance::Finite(1));
+        assert_eq!(warshall_floyd.dist(0, 3), Distance::Finite(5));
+        assert_eq!(warshall_floyd.dist(3, 2), Distance::Finite(-1));
+        assert_eq!(warshall_floyd.dist(2, 0), Distance::Unreachable);
+        assert_eq!(warshall_floyd.dist(4, 4), Distance::Finite(0));
+        assert_eq!(warshall_floyd.path(0, 3), Some(vec![0, 1, 2, 3]));
+        assert_eq!(warshall_floyd.path(3, 2), Some(vec![3, 1, 2]));
+        assert_eq!(warshall_floyd.path(2, 0), None);
+        assert_eq!(warshall_floyd.path(4, 4), Some(vec![4]));
+        assert!(!warshall_floyd.has_negative_cycle());
+
+        // 1 -> 2 -> 3 -> 1 が負閉路になる
+        let mut warshall_floyd = warshall_floyd;
+        warshall_floyd.add_edge(3, 1, -3);
+        warshall_floyd.add_edge(3, 4, 10);
+        assert!(warshall_floyd.has_negative_cycle());
+        assert_eq!(warshall_floyd.dist(0, 4), Distance::NegativeInfinity);
+        assert_eq!(warshall_floyd.dist(2, 1), Distance::NegativeInfinity);
+        assert_eq!(warshall_floyd.dist(0, 0), Distance::Finite(0));
+        assert_eq!(warshall_floyd.dist(4, 1), Distance::Unreachable);
+        assert_eq!(warshall_floyd.path(0, 4), None);
+
+        let all_dists = warshall_floyd.all_dists();
+        for from in 0..5 {
+            for to in 0..5 {
+                assert_eq!(all_dists[from][to], warshall_floyd.dist(from, to));
+            }
+        }
+    }
+
+    // Bellman-Ford法で求めた距離と比較する
+    fn naive_dists(n: usize, edges: &[(usize, usize, i64)], from: usize) -> Vec<Distance<i64>> {
+        let mut dists = vec![None; n];
+        dists[from] = Some(0);
+        for _ in 0..n {
+            for &(u, v, cost) in edges {
+                if let Some(dist) = dists[u] {
+                    if dists[v].map_or(true, |d| dist + cost < d) {
+                        dists[v] = Some(dist + cost);
+                    }
+                }
+            }
+        }
+
+        let mut negative = vec![false; n];
+        for _ in 0..n {
+            for &(u, v, cost) in edges {
+                if let Some(dist) = dists[u] {
+                    if negative[u] || dists[v].map_or(true, |d| dist + cost < d) {
+                        dists[v] = Some(dist + cost);
+                        negative[v] = true;
+                    }
+                }
+            }
+        }
+
+        (0..n)
+            .map(|v| match dists[v] {
+                None => Distance::Unreachable,
+                Some(_) if negative[v] => Distance::NegativeInfinity,
+                Some(dist) => Distance::Finite(dist),
+            })
+            .collect()
+    }
+
+    proptest! {
+        #[test]
+        fn warshall_floyd_random_test(n :u8) {
+            let n = n as usize % 8 + 1;
+            let mut edges = vec![];
+            let mut incremental = WarshallFloyd::new(n);
+            for _ in 0..n * 2 {
+                let edge = (random::<usize>() % n, random::<usize>() % n, random::<i64>() % 20 - 3);
+                edges.push(edge);
+                incremental.add_edge(edge.0, edge.1, edge.2);
+            }
+            let warshall_floyd = WarshallFloyd::from_edges(n, &edges);
+
+            for from in 0..n {
+                let expected = naive_dists(n, &edges, from);
+                for to in 0..n {
+                    let expected = expected[to];
+                    prop_assert_eq!(warshall_floyd.dist(from, to), expected);
+                    prop_assert_eq!(incremental.dist(from, to), expected);
+
+                    if let Distance::Finite(dist) = expected {
+                        for path in [warshall_floyd.path(from, to).unwrap(), incremental.path(from, to).unwrap()] {
+                            let mut cost = 0;
+                            for i in 0..path.len() - 1 {
+                                cost += edges
+                                    .iter()
+                                    .filter(|e| (e.0, e.1) == (path[i], path[i + 1]))
+                                    .map(|e| e.2)
+                                    .min()
+                                    .unwrap();
+                            }
+                            prop_assert_eq!(cost, dist);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}